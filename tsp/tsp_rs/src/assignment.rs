@@ -0,0 +1,313 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::time::Instant;
+use crate::graph::Graph;
+
+// Custo usado para proibir uma atribuicao (a diagonal, para nao ligar uma cidade a ela mesma)
+const FORBIDDEN: i64 = i64::MAX / 4;
+
+// Resultado do problema de atribuicao: cada vertice i é ligado ao vertice successors[i].
+// Os potenciais formam a solucao dual: row[i] + column[j] <= custo(i, j) e a soma de todos é o custo otimo.
+pub struct Assignment {
+    successors: Vec<usize>,
+    cost: u64,
+    row_potentials: Vec<i64>,
+    column_potentials: Vec<i64>
+}
+
+impl Assignment {
+    pub fn get_successors(&self) -> &Vec<usize> {
+        &self.successors
+    }
+
+    pub fn get_cost(&self) -> u64 {
+        self.cost
+    }
+
+    pub fn get_row_potentials(&self) -> &Vec<i64> {
+        &self.row_potentials
+    }
+
+    pub fn get_column_potentials(&self) -> &Vec<i64> {
+        &self.column_potentials
+    }
+
+    // Ciclos formados pela atribuicao (ela é uma permutacao), cada um comecando pelo menor vertice
+    pub fn get_cycles(&self) -> Vec<Vec<usize>> {
+        let mut visited: Vec<bool> = vec![false; self.successors.len()];
+        let mut cycles: Vec<Vec<usize>> = Vec::new();
+
+        for start in 0..self.successors.len() {
+            if visited[start] {
+                continue;
+            }
+
+            let mut cycle: Vec<usize> = Vec::new();
+            let mut actual_vertex: usize = start;
+
+            while !visited[actual_vertex] {
+                visited[actual_vertex] = true;
+                cycle.push(actual_vertex);
+                actual_vertex = self.successors[actual_vertex];
+            }
+
+            cycles.push(cycle);
+        }
+
+        cycles
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Algoritmo hungaro (versao O(n³) com potenciais): https://en.wikipedia.org/wiki/Hungarian_algorithm
+    // Atribui a cada vertice um sucessor diferente dele mesmo com custo total minimo. Como no
+    // `get_route_cost`, a matriz é tratada como completa: pesos 0 fora da diagonal custam 0.
+    pub fn get_assignment(&self) -> Result<Assignment, &'static str> {
+        if !self.is_squared() {
+            return Err("Graph is not squared!");
+        }
+
+        let n: usize = self.num_vertices();
+
+        if n < 2 {
+            return Err("Graph needs at least two vertices!");
+        }
+
+        // Matriz de custos indexada a partir de 1, a linha e coluna 0 sao auxiliares
+        let cost = |i: usize, j: usize| -> i64 {
+            if i == j {
+                FORBIDDEN
+            } else {
+                self.get_edge_weight(i - 1, j - 1).unwrap() as i64
+            }
+        };
+
+        let mut u: Vec<i64> = vec![0; n + 1];
+        let mut v: Vec<i64> = vec![0; n + 1];
+        // p[j]: linha atribuida a coluna j; way[j]: coluna anterior no caminho aumentante
+        let mut p: Vec<usize> = vec![0; n + 1];
+        let mut way: Vec<usize> = vec![0; n + 1];
+
+        for i in 1..=n {
+            p[0] = i;
+            let mut j0: usize = 0;
+            let mut min_v: Vec<i64> = vec![i64::MAX; n + 1];
+            let mut used: Vec<bool> = vec![false; n + 1];
+
+            // Procura um caminho aumentante a partir da linha i, ajustando os potenciais
+            loop {
+                used[j0] = true;
+                let i0: usize = p[j0];
+                let mut delta: i64 = i64::MAX;
+                let mut j1: usize = 0;
+
+                for j in 1..=n {
+                    if used[j] {
+                        continue;
+                    }
+
+                    let reduced: i64 = cost(i0, j) - u[i0] - v[j];
+
+                    if reduced < min_v[j] {
+                        min_v[j] = reduced;
+                        way[j] = j0;
+                    }
+
+                    if min_v[j] < delta {
+                        delta = min_v[j];
+                        j1 = j;
+                    }
+                }
+
+                for j in 0..=n {
+                    if used[j] {
+                        u[p[j]] += delta;
+                        v[j] -= delta;
+                    } else {
+                        min_v[j] -= delta;
+                    }
+                }
+
+                j0 = j1;
+
+                if p[j0] == 0 {
+                    break;
+                }
+            }
+
+            // Inverte o caminho aumentante
+            loop {
+                let j1: usize = way[j0];
+                p[j0] = p[j1];
+                j0 = j1;
+
+                if j0 == 0 {
+                    break;
+                }
+            }
+        }
+
+        let mut successors: Vec<usize> = vec![0; n];
+
+        for j in 1..=n {
+            successors[p[j] - 1] = j - 1;
+        }
+
+        let cost: u64 = (0..n).map(|i| self.get_edge_weight(i, successors[i]).unwrap()).sum();
+
+        Ok(Assignment {
+            successors,
+            cost,
+            row_potentials: u[1..].to_vec(),
+            column_potentials: v[1..].to_vec()
+        })
+    }
+
+    // Limitante inferior do TSP (simetrico ou assimetrico): toda rota é uma atribuicao sem subciclos
+    pub fn get_assignment_lower_bound(&self) -> Result<u64, &'static str> {
+        Ok(self.get_assignment()?.get_cost())
+    }
+
+    // Heuristica de patching de Karp: parte dos ciclos da atribuicao otima e vai juntando o maior ciclo com
+    // outro, trocando os arcos a->a' e b->b' por a->b' e b->a' com o menor aumento de custo, ate sobrar
+    // uma unica rota. Boa para instancias assimetricas, onde a atribuicao ja fica perto do otimo.
+    pub fn tsp_assignment_patching(&self, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        self.check_complete()?;
+
+        let start_time = Instant::now();
+
+        let assignment: Assignment = self.get_assignment()?;
+        let mut successors: Vec<usize> = assignment.get_successors().clone();
+        let mut cycles: Vec<Vec<usize>> = assignment.get_cycles();
+
+        if log {
+            println!("Assignment lower bound: {} - Cycles: {}", assignment.get_cost(), cycles.len());
+        }
+
+        let weight = |i: usize, j: usize| -> i64 { self.get_edge_weight(i, j).unwrap() as i64 };
+
+        while cycles.len() > 1 {
+            let largest: usize = (0..cycles.len()).max_by_key(|&c| cycles[c].len()).unwrap();
+            // (aumento de custo, ciclo, a, b)
+            let mut best: Option<(i64, usize, usize, usize)> = None;
+
+            for (c, cycle) in cycles.iter().enumerate() {
+                if c == largest {
+                    continue;
+                }
+
+                for &a in cycles[largest].iter() {
+                    for &b in cycle.iter() {
+                        let (a_next, b_next) = (successors[a], successors[b]);
+                        let increase: i64 = weight(a, b_next) + weight(b, a_next) - weight(a, a_next) - weight(b, b_next);
+
+                        if best.is_none_or(|(actual, _, _, _)| increase < actual) {
+                            best = Some((increase, c, a, b));
+                        }
+                    }
+                }
+            }
+
+            let (_, other, a, b) = best.unwrap();
+            successors.swap(a, b);
+
+            let merged: Vec<usize> = cycles.remove(other);
+            let largest: usize = if other < largest { largest - 1 } else { largest };
+            cycles[largest].extend(merged);
+        }
+
+        let mut route: Vec<usize> = Vec::with_capacity(self.num_vertices());
+        let mut actual_vertex: usize = 0;
+
+        for _ in 0..self.num_vertices() {
+            route.push(actual_vertex);
+            actual_vertex = successors[actual_vertex];
+        }
+
+        let cost: u64 = self.get_route_cost(&route)?;
+
+        let end_time = Instant::now();
+
+        if log {
+            println!("Time elapsed: {} - Route cost: {} - Route found: {}", end_time.duration_since(start_time).as_micros(), cost, route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
+        }
+
+        Ok((route, cost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::random_graph;
+    use itertools::Itertools;
+
+    // Menor custo entre as permutacoes sem ponto fixo
+    fn brute_force_assignment(t: &Graph<usize>) -> u64 {
+        let n: usize = t.num_vertices();
+
+        (0..n).permutations(n)
+            .filter(|p| p.iter().enumerate().all(|(i, &j)| i != j))
+            .map(|p| p.iter().enumerate().map(|(i, &j)| t.weight(i, j)).sum())
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn hungarian_known_answer() {
+        // Os ciclos 0 <-> 1 e 2 <-> 3 custam 1 + 1 + 1 + 1; qualquer rota precisa de um arco de peso 9
+        let t: Graph<usize> = Graph::from((0..4).collect(), vec![vec![0, 1, 9, 9], vec![1, 0, 9, 9], vec![9, 9, 0, 1], vec![9, 9, 1, 0]]);
+        let assignment: Assignment = t.get_assignment().unwrap();
+
+        assert_eq!(assignment.get_cost(), 4);
+        assert_eq!(assignment.get_successors(), &vec![1, 0, 3, 2]);
+        assert_eq!(assignment.get_cycles(), vec![vec![0, 1], vec![2, 3]]);
+        assert_eq!(t.tsp_assignment_patching(false).unwrap().1, 20);
+    }
+
+    #[test]
+    fn hungarian_matches_brute_force() {
+        let mut seed: u64 = 29;
+
+        for n in [2, 3, 5, 7] {
+            for _ in 0..30 {
+                let t: Graph<usize> = random_graph(n, false, &mut seed);
+                let assignment: Assignment = t.get_assignment().unwrap();
+                let (rows, columns) = (assignment.get_row_potentials(), assignment.get_column_potentials());
+
+                assert_eq!(assignment.get_cost(), brute_force_assignment(&t));
+                assert_eq!(rows.iter().sum::<i64>() + columns.iter().sum::<i64>(), assignment.get_cost() as i64);
+
+                // Potenciais viaveis para o dual
+                for (i, j) in (0..n).flat_map(|i| (0..n).map(move |j| (i, j))).filter(|(i, j)| i != j) {
+                    assert!(rows[i] + columns[j] <= t.weight(i, j) as i64);
+                }
+
+                let (route, cost) = t.tsp_assignment_patching(false).unwrap();
+                assert_eq!(route.iter().copied().sorted().collect::<Vec<usize>>(), (0..n).collect::<Vec<usize>>());
+                assert_eq!(t.get_route_cost(&route), Ok(cost));
+                assert!(cost >= assignment.get_cost());
+            }
+        }
+    }
+
+    #[test]
+    fn small_graphs() {
+        let pair: Graph<usize> = Graph::from(vec![0, 1], vec![vec![0, 3], vec![5, 0]]);
+        // Como no `get_route_cost`, peso 0 fora da diagonal custa 0 na atribuicao
+        let zeros: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0; 3]; 3]);
+
+        assert!(Graph::from(vec![0], vec![vec![0]]).get_assignment().is_err());
+        assert_eq!(pair.get_assignment().unwrap().get_cost(), 8);
+        assert_eq!(pair.tsp_assignment_patching(false), Ok((vec![0, 1], 8)));
+        assert_eq!(zeros.get_assignment_lower_bound(), Ok(0));
+        assert!(zeros.tsp_assignment_patching(false).is_err());
+    }
+
+    #[test]
+    fn patching_rejects_ragged_matrix() {
+        let t: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0, 1, 2], vec![1, 0], vec![2, 1, 0]]);
+
+        assert_eq!(t.tsp_assignment_patching(false), Err("Graph is not squared!"));
+    }
+}
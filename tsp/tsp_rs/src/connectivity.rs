@@ -0,0 +1,288 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use crate::graph::Graph;
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Existe aresta entre u e v em alguma das direcoes
+    fn is_linked(&self, u: usize, v: usize) -> bool {
+        self.has_edge(u, v) || self.has_edge(v, u)
+    }
+
+    // Componentes conexas ignorando a direcao das arestas, cada uma com os vertices em ordem crescente
+    pub fn get_connected_components(&self) -> Result<Vec<Vec<usize>>, &'static str> {
+        if !self.is_squared() {
+            return Err("Graph is not squared!");
+        }
+
+        let n: usize = self.num_vertices();
+        let mut visited: Vec<bool> = vec![false; n];
+        let mut components: Vec<Vec<usize>> = Vec::new();
+
+        for root in 0..n {
+            if visited[root] {
+                continue;
+            }
+
+            let mut component: Vec<usize> = Vec::new();
+            let mut stack: Vec<usize> = vec![root];
+            visited[root] = true;
+
+            while let Some(u) = stack.pop() {
+                component.push(u);
+
+                for (v, seen) in visited.iter_mut().enumerate() {
+                    if !*seen && self.is_linked(u, v) {
+                        *seen = true;
+                        stack.push(v);
+                    }
+                }
+            }
+
+            component.sort_unstable();
+            components.push(component);
+        }
+
+        Ok(components)
+    }
+
+    // Falso tambem quando a matriz nao é quadrada
+    pub fn is_connected(&self) -> bool {
+        self.get_connected_components().is_ok_and(|components| components.len() <= 1)
+    }
+
+    // Algoritmo de Tarjan: https://en.wikipedia.org/wiki/Tarjan%27s_strongly_connected_components_algorithm
+    // Versao iterativa (sem recursao) para nao estourar a pilha em grafos grandes. Cada componente fortemente
+    // conexa sai com os vertices em ordem crescente, e as componentes em ordem topologica reversa.
+    pub fn get_strongly_connected_components(&self) -> Result<Vec<Vec<usize>>, &'static str> {
+        if !self.is_squared() {
+            return Err("Graph is not squared!");
+        }
+
+        let n: usize = self.num_vertices();
+        let mut counter: usize = 0;
+        let mut index: Vec<Option<usize>> = vec![None; n];
+        let mut low_link: Vec<usize> = vec![0; n];
+        let mut on_stack: Vec<bool> = vec![false; n];
+        let mut stack: Vec<usize> = Vec::new();
+        let mut components: Vec<Vec<usize>> = Vec::new();
+
+        for root in 0..n {
+            if index[root].is_some() {
+                continue;
+            }
+
+            // Pilha de chamadas: (vertice, proximo vizinho a olhar)
+            let mut call_stack: Vec<(usize, usize)> = vec![(root, 0)];
+            index[root] = Some(counter);
+            low_link[root] = counter;
+            counter += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&(u, next)) = call_stack.last() {
+                let w: Option<usize> = (next..n).find(|&w| self.has_edge(u, w));
+
+                if let Some(w) = w {
+                    call_stack.last_mut().unwrap().1 = w + 1;
+
+                    match index[w] {
+                        None => {
+                            index[w] = Some(counter);
+                            low_link[w] = counter;
+                            counter += 1;
+                            stack.push(w);
+                            on_stack[w] = true;
+                            call_stack.push((w, 0));
+                        },
+                        Some(w_index) => {
+                            if on_stack[w] {
+                                low_link[u] = low_link[u].min(w_index);
+                            }
+                        }
+                    }
+
+                    continue;
+                }
+
+                call_stack.pop();
+
+                if let Some(&(parent, _)) = call_stack.last() {
+                    low_link[parent] = low_link[parent].min(low_link[u]);
+                }
+
+                // u é a raiz de uma componente: desempilha ate ele
+                if Some(low_link[u]) == index[u] {
+                    let mut component: Vec<usize> = Vec::new();
+
+                    while let Some(v) = stack.pop() {
+                        on_stack[v] = false;
+                        component.push(v);
+
+                        if v == u {
+                            break;
+                        }
+                    }
+
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+
+        Ok(components)
+    }
+
+    // Todo vertice alcanca todos os outros respeitando a direcao das arestas (condicao para existir rota)
+    pub fn is_strongly_connected(&self) -> bool {
+        self.get_strongly_connected_components().is_ok_and(|components| components.len() <= 1)
+    }
+
+    // Busca em profundidade com tempos de descoberta e low-link sobre o grafo nao direcionado:
+    // https://en.wikipedia.org/wiki/Bridge_(graph_theory)#Tarjan's_bridge-finding_algorithm
+    // Retorna as pontes e os pontos de articulacao de uma vez. Espera uma matriz quadrada.
+    fn get_bridges_and_articulation_points(&self) -> (Vec<(usize, usize)>, Vec<usize>) {
+        let n: usize = self.num_vertices();
+        let mut counter: usize = 0;
+        let mut discovery: Vec<Option<usize>> = vec![None; n];
+        let mut low_link: Vec<usize> = vec![0; n];
+        let mut is_articulation: Vec<bool> = vec![false; n];
+        let mut bridges: Vec<(usize, usize)> = Vec::new();
+
+        for root in 0..n {
+            if discovery[root].is_some() {
+                continue;
+            }
+
+            let mut root_children: usize = 0;
+            // Pilha de chamadas: (vertice, pai, proximo vizinho a olhar)
+            let mut call_stack: Vec<(usize, Option<usize>, usize)> = vec![(root, None, 0)];
+            discovery[root] = Some(counter);
+            low_link[root] = counter;
+            counter += 1;
+
+            while let Some(&(u, parent, next)) = call_stack.last() {
+                let w: Option<usize> = (next..n).find(|&w| w != u && Some(w) != parent && self.is_linked(u, w));
+
+                if let Some(w) = w {
+                    call_stack.last_mut().unwrap().2 = w + 1;
+
+                    match discovery[w] {
+                        None => {
+                            discovery[w] = Some(counter);
+                            low_link[w] = counter;
+                            counter += 1;
+                            call_stack.push((w, Some(u), 0));
+
+                            if u == root {
+                                root_children += 1;
+                            }
+                        },
+                        Some(w_discovery) => low_link[u] = low_link[u].min(w_discovery)
+                    }
+
+                    continue;
+                }
+
+                call_stack.pop();
+
+                if let Some(p) = parent {
+                    let p_discovery: usize = discovery[p].unwrap();
+                    low_link[p] = low_link[p].min(low_link[u]);
+
+                    // Nenhum vertice abaixo de u volta para p ou acima: a aresta p-u é ponte
+                    if low_link[u] > p_discovery {
+                        bridges.push((p.min(u), p.max(u)));
+                    }
+
+                    if p != root && low_link[u] >= p_discovery {
+                        is_articulation[p] = true;
+                    }
+                }
+            }
+
+            // A raiz só é articulacao se tiver mais de um filho na arvore da busca
+            if root_children > 1 {
+                is_articulation[root] = true;
+            }
+        }
+
+        bridges.sort_unstable();
+
+        (bridges, (0..n).filter(|&v| is_articulation[v]).collect())
+    }
+
+    // Arestas cuja remocao desconecta o grafo (visto como nao direcionado), como pares (menor, maior)
+    pub fn get_bridges(&self) -> Result<Vec<(usize, usize)>, &'static str> {
+        if !self.is_squared() {
+            return Err("Graph is not squared!");
+        }
+
+        Ok(self.get_bridges_and_articulation_points().0)
+    }
+
+    // Vertices cuja remocao desconecta o grafo (visto como nao direcionado)
+    pub fn get_articulation_points(&self) -> Result<Vec<usize>, &'static str> {
+        if !self.is_squared() {
+            return Err("Graph is not squared!");
+        }
+
+        Ok(self.get_bridges_and_articulation_points().1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn components_bridges_and_articulation_points() {
+        // Triangulo 0-1-2 ligado por 2-3 a um caminho 3-4; o vertice 5 fica isolado
+        let mut matrix: Vec<Vec<u64>> = vec![vec![0; 6]; 6];
+
+        for (u, v) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4)] {
+            matrix[u][v] = 1;
+            matrix[v][u] = 1;
+        }
+
+        let t: Graph<usize> = Graph::from((0..6).collect(), matrix);
+
+        assert_eq!(t.get_connected_components(), Ok(vec![vec![0, 1, 2, 3, 4], vec![5]]));
+        assert_eq!(t.get_bridges(), Ok(vec![(2, 3), (3, 4)]));
+        assert_eq!(t.get_articulation_points(), Ok(vec![2, 3]));
+        assert!(!t.is_connected());
+    }
+
+    #[test]
+    fn strongly_connected_components_follow_direction() {
+        // Ciclo 0 -> 1 -> 2 -> 0 e o arco 2 -> 3 sem volta
+        let t: Graph<usize> = Graph::from((0..4).collect(), vec![vec![0, 1, 0, 0], vec![0, 0, 1, 0], vec![1, 0, 0, 1], vec![0, 0, 0, 0]]);
+
+        assert_eq!(t.get_strongly_connected_components(), Ok(vec![vec![3], vec![0, 1, 2]]));
+        assert!(t.is_connected());
+        assert!(!t.is_strongly_connected());
+    }
+
+    #[test]
+    fn small_graphs() {
+        let single: Graph<usize> = Graph::from(vec![0], vec![vec![0]]);
+        let pair: Graph<usize> = Graph::from(vec![0, 1], vec![vec![0, 0], vec![0, 0]]);
+
+        assert!(single.is_strongly_connected());
+        assert_eq!(single.get_articulation_points(), Ok(Vec::new()));
+        // Peso 0 fora da diagonal é ausencia de aresta
+        assert_eq!(pair.get_connected_components(), Ok(vec![vec![0], vec![1]]));
+        assert!(!pair.is_strongly_connected());
+    }
+
+    #[test]
+    fn ragged_matrix_is_an_error() {
+        let t: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0, 1, 2], vec![1, 0], vec![2, 1, 0]]);
+
+        assert!(t.get_connected_components().is_err());
+        assert!(t.get_strongly_connected_components().is_err());
+        assert!(t.get_bridges().is_err());
+        assert!(t.get_articulation_points().is_err());
+        assert!(!t.is_connected());
+        assert!(!t.is_strongly_connected());
+    }
+}
@@ -0,0 +1,225 @@
+// Distancias calculadas sob demanda a partir das coordenadas das cidades, seguindo as funcoes do TSPLIB:
+// http://comopt.ifi.uni-heidelberg.de/software/TSPLIB95/tsp95.pdf
+// Com isso nao e preciso guardar a matriz n x n, o que permite instancias com 100k cidades.
+
+// O TSPLIB define o valor truncado de pi para o GEO
+#[allow(clippy::approx_constant)]
+const GEO_PI: f64 = 3.141592;
+const GEO_EARTH_RADIUS: f64 = 6378.388;
+const HAVERSINE_EARTH_RADIUS: f64 = 6371.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    // EUC_2D: distancia euclidiana arredondada para o inteiro mais proximo
+    Euclidean,
+    // CEIL_2D: distancia euclidiana arredondada para cima
+    EuclideanCeil,
+    // MAN_2D
+    Manhattan,
+    // MAX_2D
+    Maximum,
+    // GEO: latitude/longitude no formato DDD.MM do TSPLIB, resultado em km
+    Geo,
+    // Latitude/longitude em graus decimais pela formula de haversine, resultado em km arredondado
+    Haversine,
+    // ATT: pseudo-euclidiana usada nas instancias att48 e att532
+    Att
+}
+
+impl Metric {
+    // Converte o valor de EDGE_WEIGHT_TYPE de um arquivo TSPLIB
+    pub fn from_tsplib(name: &str) -> Result<Self, &'static str> {
+        match name.trim() {
+            "EUC_2D" => Ok(Metric::Euclidean),
+            "CEIL_2D" => Ok(Metric::EuclideanCeil),
+            "MAN_2D" => Ok(Metric::Manhattan),
+            "MAX_2D" => Ok(Metric::Maximum),
+            "GEO" => Ok(Metric::Geo),
+            "HAVERSINE" => Ok(Metric::Haversine),
+            "ATT" => Ok(Metric::Att),
+            _ => Err("Unsupported edge weight type!")
+        }
+    }
+}
+
+pub struct Coordinates {
+    points: Vec<(f64, f64)>,
+    metric: Metric
+}
+
+impl Coordinates {
+    pub fn new(points: Vec<(f64, f64)>, metric: Metric) -> Self {
+        Coordinates {
+            points,
+            metric
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn get_metric(&self) -> Metric {
+        self.metric
+    }
+
+    pub fn get_point(&self, idx: usize) -> Result<(f64, f64), &'static str> {
+        match self.points.get(idx) {
+            Some(point) => Ok(*point),
+            None => Err("Index out of range!")
+        }
+    }
+
+    pub fn push(&mut self, point: (f64, f64)) {
+        self.points.push(point);
+    }
+
+    pub fn remove(&mut self, idx: usize) -> (f64, f64) {
+        self.points.remove(idx)
+    }
+
+    // Novo conjunto so com os pontos dos indices dados, na ordem dada
+    pub fn subset(&self, indices: &[usize]) -> Result<Self, &'static str> {
+        let points: Vec<(f64, f64)> = indices.iter().map(|&i| self.get_point(i)).collect::<Result<Vec<(f64, f64)>, &'static str>>()?;
+
+        Ok(Coordinates::new(points, self.metric))
+    }
+
+    // Os indices devem ser validos, quem chama (o Graph) ja faz essa verificacao
+    pub fn distance(&self, src_idx: usize, dst_idx: usize) -> u64 {
+        if src_idx == dst_idx {
+            return 0;
+        }
+
+        let (x1, y1) = self.points[src_idx];
+        let (x2, y2) = self.points[dst_idx];
+        let (dx, dy) = ((x1 - x2).abs(), (y1 - y2).abs());
+
+        match self.metric {
+            Metric::Euclidean => (dx * dx + dy * dy).sqrt().round() as u64,
+            Metric::EuclideanCeil => (dx * dx + dy * dy).sqrt().ceil() as u64,
+            Metric::Manhattan => (dx + dy).round() as u64,
+            Metric::Maximum => (dx.round() as u64).max(dy.round() as u64),
+            Metric::Geo => geo_distance((x1, y1), (x2, y2)),
+            Metric::Haversine => haversine_distance((x1, y1), (x2, y2)).round() as u64,
+            Metric::Att => {
+                let r: f64 = ((dx * dx + dy * dy) / 10.0).sqrt();
+                let t: f64 = r.round();
+
+                if t < r {
+                    t as u64 + 1
+                } else {
+                    t as u64
+                }
+            }
+        }
+    }
+}
+
+// Converte o formato DDD.MM do TSPLIB para radianos
+fn geo_to_radians(value: f64) -> f64 {
+    let degrees: f64 = value.trunc();
+    let minutes: f64 = value - degrees;
+
+    GEO_PI * (degrees + 5.0 * minutes / 3.0) / 180.0
+}
+
+fn geo_distance(a: (f64, f64), b: (f64, f64)) -> u64 {
+    let (lat1, long1) = (geo_to_radians(a.0), geo_to_radians(a.1));
+    let (lat2, long2) = (geo_to_radians(b.0), geo_to_radians(b.1));
+
+    let q1: f64 = (long1 - long2).cos();
+    let q2: f64 = (lat1 - lat2).cos();
+    let q3: f64 = (lat1 + lat2).cos();
+
+    (GEO_EARTH_RADIUS * (0.5 * ((1.0 + q1) * q2 - (1.0 - q1) * q3)).acos() + 1.0) as u64
+}
+
+// Distancia de grande circulo em km, pontos em (latitude, longitude) em graus decimais
+pub fn haversine_distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (lat1, lat2) = (a.0.to_radians(), b.0.to_radians());
+    let d_lat: f64 = lat2 - lat1;
+    let d_long: f64 = (b.1 - a.1).to_radians();
+
+    let h: f64 = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_long / 2.0).sin().powi(2);
+
+    2.0 * HAVERSINE_EARTH_RADIUS * h.sqrt().asin()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+
+    fn distance(a: (f64, f64), b: (f64, f64), metric: Metric) -> u64 {
+        Coordinates::new(vec![a, b], metric).distance(0, 1)
+    }
+
+    // burma14 do TSPLIB, pontos em DDD.MM
+    fn burma14() -> Vec<(f64, f64)> {
+        vec![
+            (16.47, 96.10), (16.47, 94.44), (20.09, 92.54), (22.39, 93.37), (25.23, 97.24), (22.00, 96.05), (20.47, 97.02),
+            (17.20, 96.29), (16.30, 97.38), (14.05, 98.12), (16.53, 97.38), (21.52, 95.59), (19.41, 97.13), (20.09, 94.55)
+        ]
+    }
+
+    #[test]
+    fn metric_names() {
+        assert_eq!(Metric::from_tsplib(" ATT "), Ok(Metric::Att));
+        assert_eq!(Metric::from_tsplib("CEIL_2D"), Ok(Metric::EuclideanCeil));
+        assert!(Metric::from_tsplib("EXPLICIT").is_err());
+    }
+
+    #[test]
+    fn planar_metrics_known_answer() {
+        // Distancia real 5.166
+        assert_eq!(distance((0.0, 0.0), (3.0, 4.2), Metric::Euclidean), 5);
+        assert_eq!(distance((0.0, 0.0), (3.0, 4.2), Metric::EuclideanCeil), 6);
+        assert_eq!(distance((0.0, 0.0), (3.0, 4.0), Metric::EuclideanCeil), 5);
+        // MAN_2D arredonda a soma, MAX_2D arredonda cada eixo (nint de 2.5 é 3)
+        assert_eq!(distance((0.0, 0.0), (0.4, 0.4), Metric::Manhattan), 1);
+        assert_eq!(distance((1.5, 2.5), (0.0, 0.0), Metric::Maximum), 3);
+        assert_eq!(distance((2.0, 2.0), (2.0, 2.0), Metric::Maximum), 0);
+    }
+
+    #[test]
+    fn att_rounds_up_the_pseudo_euclidean_distance() {
+        // sqrt(100 / 10) = 3.16: nint dá 3, menor que a distancia, entao soma 1
+        assert_eq!(distance((0.0, 0.0), (10.0, 0.0), Metric::Att), 4);
+        // sqrt(10 / 10) = 1 exato
+        assert_eq!(distance((0.0, 0.0), (3.0, 1.0), Metric::Att), 1);
+        // sqrt(25 / 10) = 1.58: nint dá 2, que ja é maior
+        assert_eq!(distance((0.0, 0.0), (5.0, 0.0), Metric::Att), 2);
+        // Duas primeiras cidades do att48
+        assert_eq!(distance((6734.0, 1453.0), (2233.0, 10.0), Metric::Att), 1495);
+    }
+
+    #[test]
+    fn geo_known_answer() {
+        // 16.30 é 16 graus e 30 minutos
+        assert!((geo_to_radians(16.30) - GEO_PI * 16.5 / 180.0).abs() < 1e-12);
+
+        let points: Vec<(f64, f64)> = burma14();
+
+        assert_eq!(distance(points[0], points[1], Metric::Geo), 153);
+        assert_eq!(distance(points[0], points[2], Metric::Geo), 510);
+
+        // Rota otima do burma14 (burma14.opt.tour), comprimento 3323
+        let t: Graph<usize> = Graph::from_coordinates((0..14).collect(), Coordinates::new(points, Metric::Geo)).unwrap();
+        let route: Vec<usize> = [1, 2, 14, 3, 4, 5, 6, 12, 7, 13, 8, 11, 9, 10].iter().map(|v| v - 1).collect();
+
+        assert_eq!(t.get_route_cost(&route), Ok(3323));
+    }
+
+    #[test]
+    fn haversine_known_answer() {
+        // Um grau de longitude no equador
+        assert!((haversine_distance((0.0, 0.0), (0.0, 1.0)) - 111.195).abs() < 1e-3);
+        assert_eq!(distance((0.0, 0.0), (0.0, 1.0), Metric::Haversine), 111);
+        assert_eq!(distance((0.0, 0.0), (0.0, 180.0), Metric::Haversine), 20015);
+    }
+}
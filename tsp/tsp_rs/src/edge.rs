@@ -1,3 +1,6 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Edge<V: Eq + PartialEq + Display + Clone> {
     src: V,
     dst: V,
@@ -0,0 +1,336 @@
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::hash::Hash;
+use crate::edge::Edge;
+use crate::graph::Graph;
+
+// Resultado do fluxo maximo de `source` para `sink`, usando os pesos do grafo como capacidades
+pub struct MaxFlow {
+    source: usize,
+    sink: usize,
+    value: u64,
+    // Fluxo liquido em cada arco: flows[u][v] > 0 significa fluxo de u para v
+    flows: Vec<Vec<u64>>,
+    // Lado da origem no corte minimo: vertices alcancaveis de `source` no grafo residual
+    source_side: Vec<bool>
+}
+
+impl MaxFlow {
+    pub fn get_source(&self) -> usize {
+        self.source
+    }
+
+    pub fn get_sink(&self) -> usize {
+        self.sink
+    }
+
+    pub fn get_value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn get_flow(&self, src_idx: usize, dst_idx: usize) -> u64 {
+        self.flows[src_idx][dst_idx]
+    }
+
+    // Arcos com fluxo positivo
+    pub fn get_edge_flows(&self) -> Vec<Edge<usize>> {
+        let n: usize = self.flows.len();
+
+        (0..n).flat_map(|u| (0..n).filter(move |&v| self.flows[u][v] > 0).map(move |v| Edge::new(u, v, self.flows[u][v])))
+            .collect()
+    }
+
+    pub fn is_on_source_side(&self, vertex_idx: usize) -> bool {
+        self.source_side[vertex_idx]
+    }
+
+    // Particao do corte minimo: (lado da origem, lado do destino)
+    pub fn get_min_cut(&self) -> (Vec<usize>, Vec<usize>) {
+        (0..self.source_side.len()).partition(|&v| self.source_side[v])
+    }
+}
+
+// Arvore de Gomory-Hu: para qualquer par u, v o corte minimo entre eles vale a menor aresta do caminho
+// entre u e v na arvore, e remover essa aresta separa os dois lados do corte.
+pub struct GomoryHuTree {
+    // Pai de cada vertice na arvore (a raiz 0 aponta para si mesma) e a capacidade do corte com o pai
+    parent: Vec<usize>,
+    cut_values: Vec<u64>
+}
+
+impl GomoryHuTree {
+    // Arestas (vertice, pai, valor do corte), n - 1 no total
+    pub fn get_edges(&self) -> Vec<Edge<usize>> {
+        (1..self.parent.len()).map(|v| Edge::new(v, self.parent[v], self.cut_values[v])).collect()
+    }
+
+    pub fn get_parent(&self) -> &Vec<usize> {
+        &self.parent
+    }
+
+    // Valor do corte minimo entre u e v: menor aresta no caminho entre eles na arvore
+    pub fn min_cut_value(&self, u_idx: usize, v_idx: usize) -> Result<u64, &'static str> {
+        let n: usize = self.parent.len();
+
+        if n <= u_idx || n <= v_idx {
+            return Err("Index out of range!");
+        }
+
+        if u_idx == v_idx {
+            return Err("Vertices must be different!");
+        }
+
+        // Profundidade de cada vertice para subir os dois lados ate o ancestral comum
+        let depth = |mut v: usize| -> usize {
+            let mut d: usize = 0;
+
+            while v != 0 {
+                v = self.parent[v];
+                d += 1;
+            }
+
+            d
+        };
+
+        let (mut u, mut v) = (u_idx, v_idx);
+        let (mut depth_u, mut depth_v) = (depth(u), depth(v));
+        let mut min_cut: u64 = u64::MAX;
+
+        while u != v {
+            if depth_u >= depth_v {
+                min_cut = min_cut.min(self.cut_values[u]);
+                u = self.parent[u];
+                depth_u -= 1;
+            } else {
+                min_cut = min_cut.min(self.cut_values[v]);
+                v = self.parent[v];
+                depth_v -= 1;
+            }
+        }
+
+        Ok(min_cut)
+    }
+}
+
+// Busca em profundidade do Dinic: empurra fluxo pelos arcos que avancam um nivel no grafo de niveis.
+// `next` guarda para cada vertice o proximo arco a testar, assim arcos saturados nao sao revisitados.
+fn push_flow(residual: &mut Vec<Vec<u64>>, level: &[Option<usize>], next: &mut [usize], u: usize, sink: usize, limit: u64) -> u64 {
+    if u == sink {
+        return limit;
+    }
+
+    let n: usize = residual.len();
+
+    while next[u] < n {
+        let v: usize = next[u];
+
+        if residual[u][v] > 0 && level[v].is_some() && level[v] == level[u].map(|l| l + 1) {
+            let pushed: u64 = push_flow(residual, level, next, v, sink, limit.min(residual[u][v]));
+
+            if pushed > 0 {
+                residual[u][v] -= pushed;
+                residual[v][u] += pushed;
+
+                return pushed;
+            }
+        }
+
+        next[u] += 1;
+    }
+
+    0
+}
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Algoritmo de Dinic: https://en.wikipedia.org/wiki/Dinic%27s_algorithm
+    // Os pesos das arestas sao as capacidades (arcos direcionados; num grafo simetrico cada aresta pode
+    // ser usada nos dois sentidos). O(n²·m).
+    pub fn get_max_flow(&self, source_idx: usize, sink_idx: usize) -> Result<MaxFlow, &'static str> {
+        if self.num_vertices() <= source_idx || self.num_vertices() <= sink_idx {
+            return Err("Index out of range!");
+        }
+
+        if source_idx == sink_idx {
+            return Err("Source and sink must be different!");
+        }
+
+        let n: usize = self.num_vertices();
+        let mut capacity: Vec<Vec<u64>> = vec![vec![0; n]; n];
+
+        for edge in self.edges() {
+            capacity[*edge.get_src()][*edge.get_dst()] = edge.get_weight();
+        }
+
+        let mut residual: Vec<Vec<u64>> = capacity.clone();
+        let mut value: u64 = 0;
+
+        loop {
+            // Grafo de niveis pela busca em largura no residual
+            let mut level: Vec<Option<usize>> = vec![None; n];
+            let mut queue: VecDeque<usize> = VecDeque::from([source_idx]);
+            level[source_idx] = Some(0);
+
+            while let Some(u) = queue.pop_front() {
+                for v in 0..n {
+                    if level[v].is_none() && residual[u][v] > 0 {
+                        level[v] = level[u].map(|l| l + 1);
+                        queue.push_back(v);
+                    }
+                }
+            }
+
+            // O destino nao é mais alcancavel: fluxo maximo encontrado
+            if level[sink_idx].is_none() {
+                let flows: Vec<Vec<u64>> = (0..n)
+                    .map(|u| (0..n).map(|v| capacity[u][v].saturating_sub(residual[u][v])).collect())
+                    .collect();
+
+                return Ok(MaxFlow {
+                    source: source_idx,
+                    sink: sink_idx,
+                    value,
+                    flows,
+                    source_side: level.iter().map(|l| l.is_some()).collect()
+                });
+            }
+
+            // Fluxo bloqueante
+            let mut next: Vec<usize> = vec![0; n];
+
+            loop {
+                let pushed: u64 = push_flow(&mut residual, &level, &mut next, source_idx, sink_idx, u64::MAX);
+
+                if pushed == 0 {
+                    break;
+                }
+
+                value += pushed;
+            }
+        }
+    }
+
+    // Arvore de Gomory-Hu pelo algoritmo de Gusfield: https://en.wikipedia.org/wiki/Gomory%E2%80%93Hu_tree
+    // n - 1 fluxos maximos, sem contrair vertices. Só vale para capacidades nao direcionadas.
+    pub fn get_gomory_hu_tree(&self) -> Result<GomoryHuTree, &'static str> {
+        if !self.is_symmetric() {
+            return Err("Graph is not symmetric!");
+        }
+
+        let n: usize = self.num_vertices();
+        let mut parent: Vec<usize> = vec![0; n];
+        let mut cut_values: Vec<u64> = vec![0; n];
+
+        for s in 1..n {
+            let t: usize = parent[s];
+            let flow: MaxFlow = self.get_max_flow(s, t)?;
+
+            cut_values[s] = flow.get_value();
+
+            // Os vertices do lado de s que estavam pendurados em t passam a ficar pendurados em s
+            for (i, p) in parent.iter_mut().enumerate() {
+                if i != s && flow.is_on_source_side(i) && *p == t {
+                    *p = s;
+                }
+            }
+
+            // Se o pai de t caiu do lado de s, s assume o lugar de t na arvore
+            if flow.is_on_source_side(parent[t]) {
+                parent[s] = parent[t];
+                parent[t] = s;
+                cut_values[s] = cut_values[t];
+                cut_values[t] = flow.get_value();
+            }
+        }
+
+        Ok(GomoryHuTree {
+            parent,
+            cut_values
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::next_random;
+
+    fn random_symmetric_graph(n: usize, seed: &mut u64) -> Graph<usize> {
+        let mut matrix: Vec<Vec<u64>> = vec![vec![0; n]; n];
+
+        for (i, j) in (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j))) {
+            let weight: u64 = next_random(seed) % 12;
+            matrix[i][j] = weight.saturating_sub(4);
+            matrix[j][i] = matrix[i][j];
+        }
+
+        Graph::from((0..n).collect(), matrix)
+    }
+
+    // Menor corte entre s e t testando todos os subconjuntos que contem s e nao contem t
+    fn brute_force_min_cut(t: &Graph<usize>, s: usize, sink: usize) -> u64 {
+        let n: usize = t.num_vertices();
+
+        (0..1usize << n)
+            .filter(|mask| mask & (1 << s) != 0 && mask & (1 << sink) == 0)
+            .map(|mask| t.edges().filter(|e| mask & (1 << e.get_src()) != 0 && mask & (1 << e.get_dst()) == 0).map(|e| e.get_weight()).sum())
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn dinic_known_answer() {
+        // Exemplo classico do CLRS, fluxo maximo 23
+        let t: Graph<usize> = Graph::from((0..6).collect(), vec![
+            vec![0, 16, 13, 0, 0, 0],
+            vec![0, 0, 10, 12, 0, 0],
+            vec![0, 4, 0, 0, 14, 0],
+            vec![0, 0, 9, 0, 0, 20],
+            vec![0, 0, 0, 7, 0, 4],
+            vec![0, 0, 0, 0, 0, 0]
+        ]);
+        let flow: MaxFlow = t.get_max_flow(0, 5).unwrap();
+
+        assert_eq!(flow.get_value(), 23);
+        assert_eq!(flow.get_min_cut(), (vec![0, 1, 2, 4], vec![3, 5]));
+
+        // Conservacao do fluxo nos vertices internos
+        for v in 1..5 {
+            let incoming: u64 = (0..6).map(|u| flow.get_flow(u, v)).sum();
+            let outgoing: u64 = (0..6).map(|w| flow.get_flow(v, w)).sum();
+            assert_eq!(incoming, outgoing);
+        }
+    }
+
+    #[test]
+    fn small_graphs() {
+        let pair: Graph<usize> = Graph::from(vec![0, 1], vec![vec![0, 5], vec![5, 0]]);
+        // Peso 0 fora da diagonal é ausencia de aresta
+        let disconnected: Graph<usize> = Graph::from(vec![0, 1], vec![vec![0, 0], vec![0, 0]]);
+        let single: Graph<usize> = Graph::from(vec![0], vec![vec![0]]);
+
+        assert_eq!(pair.get_max_flow(0, 1).unwrap().get_value(), 5);
+        assert_eq!(disconnected.get_max_flow(0, 1).unwrap().get_value(), 0);
+        assert!(single.get_max_flow(0, 0).is_err());
+        assert!(single.get_gomory_hu_tree().unwrap().get_edges().is_empty());
+        assert_eq!(pair.get_gomory_hu_tree().unwrap().min_cut_value(1, 0), Ok(5));
+    }
+
+    #[test]
+    fn cuts_match_brute_force() {
+        let mut seed: u64 = 23;
+
+        for _ in 0..60 {
+            let t: Graph<usize> = random_symmetric_graph(7, &mut seed);
+            let tree: GomoryHuTree = t.get_gomory_hu_tree().unwrap();
+
+            assert_eq!(tree.get_edges().len(), 6);
+
+            for (u, v) in (0..7).flat_map(|u| (u + 1..7).map(move |v| (u, v))) {
+                let expected: u64 = brute_force_min_cut(&t, u, v);
+
+                assert_eq!(t.get_max_flow(u, v).unwrap().get_value(), expected);
+                assert_eq!(tree.min_cut_value(u, v), Ok(expected));
+            }
+        }
+    }
+}
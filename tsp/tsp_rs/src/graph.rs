@@ -1,48 +1,223 @@
-use std::collections::{BinaryHeap, HashSet, VecDeque};
-use std::cmp::{Reverse, Eq};
-use std::convert::TryInto;
+use std::collections::{HashMap, HashSet};
+use std::cmp::Eq;
 use std::clone::Clone;
 use std::fmt::Display;
+use std::hash::Hash;
 use std::time::Instant;
 use itertools::Itertools;
+use crate::multigraph::Multigraph;
+use crate::coordinates::Coordinates;
+use crate::edge::Edge;
+use crate::mst::SpanningTree;
 
-pub struct Graph<V: Eq + PartialEq + Display + Clone> {
+pub struct Graph<V: Eq + PartialEq + Hash + Display + Clone> {
     vertices: Vec<V>,
-    adjacency_matrix: Vec<Vec<u64>>
+    // Mapa rotulo -> indice, mantido em sincronia com `vertices`
+    indices: HashMap<V, usize>,
+    adjacency_matrix: Vec<Vec<u64>>,
+    // Quando presente os pesos sao calculados a partir das coordenadas e a matriz fica vazia
+    coordinates: Option<Coordinates>
 }
 
-impl<V: Eq + Display + Clone> Graph<V> {
+impl<V: Eq + Hash + Display + Clone> Default for Graph<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
     pub fn new() -> Self {
         Graph {
             vertices: Vec::<V>::new(),
-            adjacency_matrix: Vec::<Vec<u64>>::new()
+            indices: HashMap::<V, usize>::new(),
+            adjacency_matrix: Vec::<Vec<u64>>::new(),
+            coordinates: None
         }
     }
 
+    // Os rotulos precisam ser unicos, senao o mapa de indices fica inconsistente: entra em panico se repetirem.
+    // Para dados vindos do usuario use `try_from`.
     pub fn from(vertices: Vec<V>, adjacency_matrix: Vec<Vec<u64>>) -> Self {
-        Graph {
-            vertices: vertices,
-            adjacency_matrix
+        Graph::try_from(vertices, adjacency_matrix).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // Mesmo que `from`, mas retorna erro quando os rotulos se repetem
+    pub fn try_from(vertices: Vec<V>, adjacency_matrix: Vec<Vec<u64>>) -> Result<Self, &'static str> {
+        let indices: HashMap<V, usize> = vertices.iter().cloned().enumerate().map(|(i, v)| (v, i)).collect();
+
+        if indices.len() != vertices.len() {
+            return Err("Duplicate vertex labels!");
+        }
+
+        Ok(Graph {
+            vertices,
+            indices,
+            adjacency_matrix,
+            coordinates: None
+        })
+    }
+
+    // Grafo completo implicito: o peso de cada aresta é a distancia entre as coordenadas dos vertices
+    pub fn from_coordinates(vertices: Vec<V>, coordinates: Coordinates) -> Result<Self, &'static str> {
+        if vertices.len() != coordinates.len() {
+            return Err("Number of vertices and coordinates differ!");
+        }
+
+        if vertices.iter().collect::<HashSet<&V>>().len() != vertices.len() {
+            return Err("Duplicate vertex labels!");
+        }
+
+        let mut graph: Graph<V> = Graph::from(vertices, Vec::new());
+        graph.coordinates = Some(coordinates);
+
+        Ok(graph)
+    }
+
+    pub fn is_implicit(&self) -> bool {
+        self.coordinates.is_some()
+    }
+
+    pub fn get_coordinates(&self) -> Option<&Coordinates> {
+        self.coordinates.as_ref()
+    }
+
+    // Gera a matriz de adjacencia explicita de um grafo baseado em coordenadas
+    pub fn materialize(&mut self) {
+        if let Some(coordinates) = self.coordinates.take() {
+            let n: usize = coordinates.len();
+
+            self.adjacency_matrix = (0..n).map(|i| (0..n).map(|j| coordinates.distance(i, j)).collect()).collect();
+        }
+    }
+
+    // Junta o grafo `t` neste, unindo os vertices com o mesmo rotulo. As arestas de `t` (peso nao nulo)
+    // sobrescrevem as existentes. Retorna, para cada indice de `t`, o indice correspondente neste grafo.
+    pub fn union(&mut self, t: &Self) -> Result<Vec<usize>, &'static str> {
+        let mapping: Vec<Option<usize>> = t.vertices().map(|(_, v)| self.index_of(v).ok()).collect();
+
+        self.union_with_mapping(t, &mapping)
+    }
+
+    // Junta o grafo `t` neste usando um mapeamento explicito: o vertice i de `t` vira o vertice `mapping[i]`
+    // deste grafo, ou um vertice novo quando `mapping[i]` é None. Retorna o mapeamento final.
+    pub fn union_with_mapping(&mut self, t: &Self, mapping: &[Option<usize>]) -> Result<Vec<usize>, &'static str> {
+        if mapping.len() != t.num_vertices() {
+            return Err("Mapping size differs from the number of vertices!");
+        }
+
+        let mut final_mapping: Vec<usize> = Vec::with_capacity(t.num_vertices());
+
+        for (i, vertex) in t.vertices() {
+            let vertex_idx: usize = match mapping[i] {
+                Some(idx) => {
+                    self.get_vertex(idx)?;
+                    idx
+                },
+                None => self.insert_vertex(vertex.clone())?
+            };
+
+            final_mapping.push(vertex_idx);
+        }
+
+        for edge in t.edges() {
+            self.insert_edge(final_mapping[*edge.get_src()], final_mapping[*edge.get_dst()], edge.get_weight(), true)?;
         }
+
+        Ok(final_mapping)
     }
 
-    pub fn union(&mut self, &t: Self) -> Result<(), &'static str> {
-        for i in 0..*t.num_vertices() {
-            self.insert_vertex(*t.get_vertex(i)?.clone());
+    // Subgrafo induzido pelos vertices de `subset`: o vertice `subset[k]` vira o vertice k do subgrafo.
+    // Retorna tambem o mapeamento de cada indice antigo para o novo (None se ficou de fora).
+    pub fn induced_subgraph(&self, subset: &[usize]) -> Result<(Self, Vec<Option<usize>>), &'static str> {
+        let mut mapping: Vec<Option<usize>> = vec![None; self.num_vertices()];
+
+        for (k, &vertex_idx) in subset.iter().enumerate() {
+            if self.num_vertices() <= vertex_idx {
+                return Err("Index out of range!");
+            }
 
-            for j in 0..*t.num_vertices() {
-                self.insert_edge(i, j, *t.get_edge_weight(i, j)?, false)?;
+            if mapping[vertex_idx].is_some() {
+                return Err("Repeated vertex in subset!");
             }
+
+            mapping[vertex_idx] = Some(k);
         }
 
-        Ok(())
+        let vertices: Vec<V> = subset.iter().map(|&i| self.vertices[i].clone()).collect();
+
+        let subgraph: Graph<V> = match &self.coordinates {
+            Some(coordinates) => Graph::from_coordinates(vertices, coordinates.subset(subset)?)?,
+            None => {
+                let adjacency_matrix: Vec<Vec<u64>> = subset.iter()
+                    .map(|&i| subset.iter().map(|&j| self.adjacency_matrix[i][j]).collect())
+                    .collect();
+
+                Graph::from(vertices, adjacency_matrix)
+            }
+        };
+
+        Ok((subgraph, mapping))
     }
 
+    // Complemento: liga com peso `edge_weight` os pares distintos que nao tem aresta e remove as existentes.
+    // Os indices dos vertices nao mudam.
+    pub fn complement(&self, edge_weight: u64) -> Self {
+        let n: usize = self.num_vertices();
+
+        let adjacency_matrix: Vec<Vec<u64>> = (0..n)
+            .map(|i| (0..n).map(|j| if i != j && !self.has_edge(i, j) { edge_weight } else { 0 }).collect())
+            .collect();
+
+        Graph::from(self.vertices.clone(), adjacency_matrix)
+    }
+
+    // Contrai a aresta (u, v): v é fundido em u, que mantem o seu rotulo. Arestas paralelas resultantes
+    // ficam com o menor peso e o laco u-v some. Retorna o mapeamento de cada indice antigo para o novo.
+    pub fn contract(&self, u_idx: usize, v_idx: usize) -> Result<(Self, Vec<usize>), &'static str> {
+        if self.num_vertices() <= u_idx || self.num_vertices() <= v_idx {
+            return Err("Index out of range!");
+        }
+
+        if u_idx == v_idx {
+            return Err("Cannot contract a vertex with itself!");
+        }
+
+        let mapping: Vec<usize> = (0..self.num_vertices()).map(|i| {
+            let i: usize = if i == v_idx { u_idx } else { i };
+
+            if i > v_idx { i - 1 } else { i }
+        }).collect();
+
+        let n: usize = self.num_vertices() - 1;
+        let mut adjacency_matrix: Vec<Vec<u64>> = vec![vec![0; n]; n];
+
+        for edge in self.edges() {
+            let (src, dst) = (mapping[*edge.get_src()], mapping[*edge.get_dst()]);
+
+            if src == dst {
+                continue;
+            }
+
+            if adjacency_matrix[src][dst] == 0 || edge.get_weight() < adjacency_matrix[src][dst] {
+                adjacency_matrix[src][dst] = edge.get_weight();
+            }
+        }
+
+        let vertices: Vec<V> = self.vertices().filter(|(i, _)| *i != v_idx).map(|(_, v)| v.clone()).collect();
+
+        Ok((Graph::from(vertices, adjacency_matrix), mapping))
+    }
+
+    // Em grafos baseados em coordenadas a matriz é vazia, use `get_edge_weight`
     pub fn get_adjacency_matrix(&self) -> &Vec<Vec<u64>> {
         &self.adjacency_matrix
     }
 
     pub fn is_squared(&self) -> bool {
+        if self.is_implicit() {
+            return true;
+        }
+
         for i in 0..self.adjacency_matrix.len() {
             if self.adjacency_matrix[i].len() != self.adjacency_matrix.len() {
                 return false;
@@ -57,6 +232,11 @@ impl<V: Eq + Display + Clone> Graph<V> {
             return false;
         }
 
+        // Todas as metricas de coordenadas sao simetricas
+        if self.is_implicit() {
+            return true;
+        }
+
         for i in 0..self.adjacency_matrix.len() {
             for j in 0..self.adjacency_matrix.len() {
                 if self.adjacency_matrix[i][j] != self.adjacency_matrix[j][i] {
@@ -68,24 +248,130 @@ impl<V: Eq + Display + Clone> Graph<V> {
         true
     }
 
-    pub fn insert_vertex(&mut self, vertex: V) {
-        self.vertices.push(vertex);
+    // Se todo par de vertices distintos tem arco nos dois sentidos (ver `has_edge`)
+    pub fn is_complete(&self) -> bool {
+        if !self.is_squared() {
+            return false;
+        }
 
-        self.adjacency_matrix.push(vec![0; self.vertices.len()]);
+        let n: usize = self.num_vertices();
+
+        (0..n).all(|i| (0..n).all(|j| i == j || self.has_edge(i, j)))
+    }
+
+    // Verificacao comum dos algoritmos que montam rotas: eles podem ligar qualquer par de vertices, entao o grafo
+    // precisa ser completo. Numa matriz o peso 0 fora da diagonal é falta de arco, nao um arco de graca.
+    pub(crate) fn check_complete(&self) -> Result<(), &'static str> {
+        if !self.is_squared() {
+            return Err("Graph is not squared!");
+        }
+
+        if !self.is_complete() {
+            return Err("Graph is not complete!");
+        }
+
+        Ok(())
+    }
+
+    // Insere um vertice novo e retorna o seu indice
+    pub fn insert_vertex(&mut self, vertex: V) -> Result<usize, &'static str> {
+        if self.is_implicit() {
+            return Err("Graph is coordinate-based, use insert_vertex_at!");
+        }
+
+        if self.indices.contains_key(&vertex) {
+            return Err("Vertex already exists!");
+        }
+
+        let vertex_idx: usize = self.vertices.len();
+
+        self.indices.insert(vertex.clone(), vertex_idx);
+        self.vertices.push(vertex);
 
         for i in 0..self.adjacency_matrix.len() {
             self.adjacency_matrix[i].push(0);
         }
+
+        self.adjacency_matrix.push(vec![0; self.vertices.len()]);
+
+        Ok(vertex_idx)
+    }
+
+    // Insere um vertice novo em um grafo baseado em coordenadas
+    pub fn insert_vertex_at(&mut self, vertex: V, point: (f64, f64)) -> Result<usize, &'static str> {
+        if !self.is_implicit() {
+            return Err("Graph is not coordinate-based!");
+        }
+
+        if self.indices.contains_key(&vertex) {
+            return Err("Vertex already exists!");
+        }
+
+        let vertex_idx: usize = self.vertices.len();
+
+        self.indices.insert(vertex.clone(), vertex_idx);
+        self.vertices.push(vertex);
+
+        if let Some(coordinates) = self.coordinates.as_mut() {
+            coordinates.push(point);
+        }
+
+        Ok(vertex_idx)
     }
 
-    pub fn remove_vertex(&mut self, vertex_index: usize) -> Result<(), &'static str> {
-        if self.vertices.len() <= vertex_index {
+    // Remove o vertice junto com sua linha e coluna da matriz de adjacencia.
+    // Os indices maiores que o removido sao deslocados em uma posicao, o vetor retornado
+    // mapeia cada indice antigo para o novo (None para o vertice removido).
+    pub fn remove_vertex(&mut self, vertex_idx: usize) -> Result<Vec<Option<usize>>, &'static str> {
+        if self.vertices.len() <= vertex_idx {
             return Err("Index out of range!");
         }
 
-        self.vertices.remove(vertex_index);
+        let removed: V = self.vertices.remove(vertex_idx);
+        self.indices.remove(&removed);
 
-        Ok(())
+        if let Some(coordinates) = self.coordinates.as_mut() {
+            coordinates.remove(vertex_idx);
+        } else {
+            self.adjacency_matrix.remove(vertex_idx);
+
+            for row in self.adjacency_matrix.iter_mut() {
+                row.remove(vertex_idx);
+            }
+        }
+
+        for (i, vertex) in self.vertices.iter().enumerate().skip(vertex_idx) {
+            self.indices.insert(vertex.clone(), i);
+        }
+
+        let mapping: Vec<Option<usize>> = (0..self.vertices.len() + 1).map(|i| {
+            if i < vertex_idx {
+                Some(i)
+            } else if i == vertex_idx {
+                None
+            } else {
+                Some(i - 1)
+            }
+        }).collect();
+
+        Ok(mapping)
+    }
+
+    pub fn remove_vertex_by_label(&mut self, vertex: &V) -> Result<Vec<Option<usize>>, &'static str> {
+        let vertex_idx: usize = self.index_of(vertex)?;
+
+        self.remove_vertex(vertex_idx)
+    }
+
+    pub fn index_of(&self, vertex: &V) -> Result<usize, &'static str> {
+        match self.indices.get(vertex) {
+            Some(idx) => Ok(*idx),
+            None => Err("Vertex not found!")
+        }
+    }
+
+    pub fn contains_vertex(&self, vertex: &V) -> bool {
+        self.indices.contains_key(vertex)
     }
 
     pub fn get_vertex(&self, vertex_idx: usize) -> Result<&V, &'static str> {
@@ -96,69 +382,141 @@ impl<V: Eq + Display + Clone> Graph<V> {
         Ok(&self.vertices[vertex_idx])
     }
 
+    // Com `directed` o arco vale so de src para dst; sem ele o peso é gravado nos dois sentidos (aresta nao direcionada)
     pub fn insert_edge(&mut self, src_idx: usize, dst_idx: usize, edge_weight: u64, directed: bool) -> Result<(), &'static str> {
         if self.vertices.len() <= src_idx || self.vertices.len() <= dst_idx {
             return Err("Index out of range!");
         }
 
+        if self.is_implicit() {
+            return Err("Graph is coordinate-based!");
+        }
+
         self.adjacency_matrix[src_idx][dst_idx] = edge_weight;
         
-        if directed == true {
+        if !directed {
             self.adjacency_matrix[dst_idx][src_idx] = edge_weight;
         }
 
         Ok(())
     }
 
-    pub fn remove_edge(&mut self, src_idx: usize, dst_idx: uselfsize, directed: bool) -> Result<(), &'static str> {
+    pub fn insert_edge_by_label(&mut self, src: &V, dst: &V, edge_weight: u64, directed: bool) -> Result<(), &'static str> {
+        let src_idx: usize = self.index_of(src)?;
+        let dst_idx: usize = self.index_of(dst)?;
+
+        self.insert_edge(src_idx, dst_idx, edge_weight, directed)
+    }
+
+    // Com `directed` remove so o arco src -> dst; sem ele remove os dois sentidos
+    pub fn remove_edge(&mut self, src_idx: usize, dst_idx: usize, directed: bool) -> Result<(), &'static str> {
         if self.num_vertices() <= src_idx || self.num_vertices() <= dst_idx {
             return Err("Index out of range!");
         }
 
+        if self.is_implicit() {
+            return Err("Graph is coordinate-based!");
+        }
+
         self.adjacency_matrix[src_idx][dst_idx] = 0;
         
-        if directed == true {
+        if !directed {
             self.adjacency_matrix[dst_idx][src_idx] = 0;
         }        
 
         Ok(())
     }
 
+    pub fn remove_edge_by_label(&mut self, src: &V, dst: &V, directed: bool) -> Result<(), &'static str> {
+        let src_idx: usize = self.index_of(src)?;
+        let dst_idx: usize = self.index_of(dst)?;
+
+        self.remove_edge(src_idx, dst_idx, directed)
+    }
+
     pub fn get_edge_weight(&self, src_idx: usize, dst_idx: usize) -> Result<u64, &'static str> {
         if self.num_vertices() <= src_idx || self.num_vertices() <= dst_idx {
             return Err("Index out of range!");
         }
 
+        if let Some(coordinates) = &self.coordinates {
+            return Ok(coordinates.distance(src_idx, dst_idx));
+        }
+
         let weight: u64 = self.adjacency_matrix[src_idx][dst_idx];
 
         Ok(weight)
     }
 
+    pub fn get_edge_weight_by_label(&self, src: &V, dst: &V) -> Result<u64, &'static str> {
+        self.get_edge_weight(self.index_of(src)?, self.index_of(dst)?)
+    }
+
     pub fn get_adjacent_vertices(&self, vertex_idx: usize) -> Result<Vec<usize>, &'static str> {
+        Ok(self.neighbors(vertex_idx)?.map(|(idx, _)| idx).collect())
+    }
+
+    // Peso sem verificacao de indices, usado pelos iteradores
+    pub(crate) fn weight(&self, src_idx: usize, dst_idx: usize) -> u64 {
+        match &self.coordinates {
+            Some(coordinates) => coordinates.distance(src_idx, dst_idx),
+            None => self.adjacency_matrix[src_idx][dst_idx]
+        }
+    }
+
+    // Se existe o arco src -> dst, sem verificacao de indices. Na matriz o peso 0 indica ausencia de aresta; no
+    // grafo baseado em coordenadas todo par distinto é aresta, mesmo com distancia arredondada para 0.
+    pub(crate) fn has_edge(&self, src_idx: usize, dst_idx: usize) -> bool {
+        match &self.coordinates {
+            Some(_) => src_idx != dst_idx,
+            None => self.adjacency_matrix[src_idx][dst_idx] != 0
+        }
+    }
+
+    // Pares (indice, rotulo) de todos os vertices
+    pub fn vertices(&self) -> impl Iterator<Item = (usize, &V)> + '_ {
+        self.vertices.iter().enumerate()
+    }
+
+    // Todas as arestas (peso nao nulo na matriz), por indice. Como a matriz é direcionada, num grafo simetrico
+    // cada aresta aparece uma vez em cada direcao.
+    pub fn edges(&self) -> impl Iterator<Item = Edge<usize>> + '_ {
+        let n: usize = self.num_vertices();
+
+        (0..n).flat_map(move |i| (0..n).filter(move |&j| self.has_edge(i, j)).map(move |j| Edge::new(i, j, self.weight(i, j))))
+    }
+
+    // Cada par {i, j} uma unica vez (i < j), olhando as duas direcoes da matriz.
+    // Usado pelos algoritmos que tratam o grafo como nao direcionado.
+    pub fn undirected_edges(&self) -> impl Iterator<Item = Edge<usize>> + '_ {
+        let n: usize = self.num_vertices();
+
+        (0..n).flat_map(move |i| (i + 1..n).filter_map(move |j| match (self.has_edge(i, j), self.has_edge(j, i)) {
+            (false, false) => None,
+            (true, _) => Some(Edge::new(i, j, self.weight(i, j))),
+            (false, true) => Some(Edge::new(i, j, self.weight(j, i)))
+        }))
+    }
+
+    // Pares (vizinho, peso) das arestas que saem do vertice
+    pub fn neighbors(&self, vertex_idx: usize) -> Result<impl Iterator<Item = (usize, u64)> + '_, &'static str> {
         if self.num_vertices() <= vertex_idx {
             return Err("Index out of range!");
         }
 
-        let mut adjacent_vertices: Vec<usize> = Vec::new();
-        
-        for i in 0..self.num_vertices() {
-            if self.get_edge_weight(vertex_idx, i).unwrap() > 0 {
-                adjacent_vertices.push(i);
-            }
-        }
+        Ok((0..self.num_vertices()).filter(move |&i| self.has_edge(vertex_idx, i)).map(move |i| (i, self.weight(vertex_idx, i))))
+    }
 
-        Ok(adjacent_vertices)
+    // Arestas que saem do vertice
+    pub fn incident_edges(&self, vertex_idx: usize) -> Result<impl Iterator<Item = Edge<usize>> + '_, &'static str> {
+        Ok(self.neighbors(vertex_idx)?.map(move |(i, w)| Edge::new(vertex_idx, i, w)))
     }
 
     pub fn num_vertices(&self) -> usize {
         self.vertices.len()
     }
 
-    pub fn get_random_route(&self) -> Result<Vec<usize>, &'static str> {
-        todo!()
-    }
-
-    pub fn get_route_cost(&self, route: &Vec<usize>) -> Result<u64, &'static str> {
+    pub fn get_route_cost(&self, route: &[usize]) -> Result<u64, &'static str> {
         if !self.is_squared() {
             return Err("Graph is not squared!");
         }
@@ -174,7 +532,7 @@ impl<V: Eq + Display + Clone> Graph<V> {
         Ok(cost)
     }
 
-    pub fn get_route_cost_2(&self, route: &Vec<&usize>) -> Result<u64, &'static str> {
+    pub fn get_route_cost_2(&self, route: &[&usize]) -> Result<u64, &'static str> {
         if !self.is_squared() {
             return Err("Graph is not squared!");
         }
@@ -190,445 +548,403 @@ impl<V: Eq + Display + Clone> Graph<V> {
         Ok(cost)
     }
 
-    // Fleury algorithm: https://en.wikipedia.org/wiki/Eulerian_path#Fleury's_algorithm
+    // Circuito euleriano do grafo visto como nao direcionado, a partir do vertice 0.
+    // Para combinar arestas repetidas (ex: MST + emparelhamento) use o Multigraph diretamente.
     pub fn get_eulerian_path(&self) -> Result<Vec<usize>, &'static str> {
-        let mut eulerian_path: Vec<usize> = Vec::new();
-
-        for i in 0..self.num_vertices() {
-            if self.get_adjacent_vertices(i)?.len() % 2 != 0 {
-                return Err("Graph does not have an eulerian path!");
-            }
+        if self.num_vertices() == 0 {
+            return Ok(Vec::new());
         }
 
-        
+        Multigraph::from_graph(self)?.get_eulerian_circuit(0)
     }
 
-    pub fn get_dijkstra_path(&self, src_idx: usize, dst_idx: usize) -> Result<VecDeque<usize>, &'static str> {
-        if self.vertices.len() <= src_idx || self.vertices.len() <= dst_idx {
-            return Err("Index out of range!");
-        }
+    pub fn tsp_brute_force(&self, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        self.check_complete()?;
 
-        let mut previous_vertex: Vec<Option<usize>> = vec![None; self.vertices.len()];
-        let mut path_cost: Vec<Option<usize>> = vec![None; self.vertices.len()];
-        let mut is_closed: Vec<bool> = vec![false; self.vertices.len()];
-        let mut vert_to_visit: BinaryHeap<Reverse<(u64, usize)>> = BinaryHeap::new();
-        let mut vertex_idx: usize;
+        let first_route: Vec<usize> = (0..self.num_vertices()).collect();
+        let mut actual_cost: u64 = self.get_route_cost(&first_route)?;
+        let mut best_route: Vec<usize> = first_route.clone();
+        let mut best_cost: u64 = actual_cost;
+        let mut counter: usize = 0;
 
-        // custo do no inicial é 0
-        path_cost[src_idx] = Some(0);
-        // insere o no inicial na heap
-        vert_to_visit.push(Reverse((0, src_idx)));
+        let start_time = Instant::now();
 
-        loop {
-            // remove o primeiro nó da heap
-            vertex_idx = match vert_to_visit.pop() {
-                Some(Reverse((_, v))) => v,
-                None => break
-            };
+        // O ultimo vertice fica fixo no fim da rota, pois o ciclo é o mesmo independente do ponto de partida
+        let last_vertex: usize = self.num_vertices() - 1;
+        let permutations = first_route[..last_vertex].iter().permutations(last_vertex);
 
-            // verifica se esse no ja nao esta fechado
-            if is_closed[vertex_idx] {
-                continue;
+        for mut permutation in permutations {
+            counter += 1;
+            permutation.push(&last_vertex);
+            actual_cost = self.get_route_cost_2(&permutation)?;
+
+            if actual_cost < best_cost {
+                best_cost = actual_cost;
+                best_route = permutation.into_iter().cloned().collect();
             }
 
-            // fecha o no
-            is_closed[vertex_idx] = true;
-
-            // verifica os adjacentes desse no, atualiza seus pesos e adiciona na heap
-            match self.get_adjacent_vertices(vertex_idx) {
-                Ok(list) => {
-                    for idx in list.iter() {
-                        if is_closed[*idx] {
-                            continue;
-                        }
-
-                        if self.get_edge_weight(vertex_idx, *idx).unwrap() < 0 {
-                            continue;
-                        }
-
-                        let total_cost: usize = match path_cost[vertex_idx] {
-                            Some(cost_v_idx) => cost_v_idx + self.get_edge_weight(vertex_idx, *idx).unwrap() as usize,
-                            None => self.get_edge_weight(vertex_idx, *idx).unwrap() as usize
-                        };
-
-                        match path_cost[*idx] {
-                            Some(cost) => {
-                                if cost > total_cost {
-                                    path_cost[*idx] = Some(total_cost);
-                                    previous_vertex[*idx] = Some(vertex_idx);
-                                    vert_to_visit.push(Reverse((total_cost.try_into().unwrap(), *idx)));
-                                }
-                            },
-                            None => {
-                                path_cost[*idx] = Some(total_cost);
-                                previous_vertex[*idx] = Some(vertex_idx);
-                                vert_to_visit.push(Reverse((total_cost.try_into().unwrap(), *idx)));
-                            }
-                        }
-                    }
-                },
-                Err(msg) => return Err(msg)
+            if log && counter.is_multiple_of(10000) {
+                println!("Iteration: {} - Time elapsed: {} - Route cost: {}", counter, Instant::now().duration_since(start_time).as_micros(), best_cost);
             }
         }
+        
+        let end_time = Instant::now();
 
-        let mut path: VecDeque<usize> = VecDeque::new();
-        path.push_front(dst_idx);
-        let mut actual_vertex: usize = dst_idx;
-
-        loop {
-            actual_vertex = match previous_vertex[actual_vertex] {
-                Some(v_idx) => v_idx,
-                None => break
-            };
-
-            path.push_front(actual_vertex);
+        if log {
+            println!("Total iterations: {} - Time elapsed: {} - Best route cost: {} - Best route found: {}", counter, end_time.duration_since(start_time).as_micros(), best_cost, best_route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
         }
 
-        Ok(path)
+        Ok((best_route, best_cost))
     }
 
-    // Algoritmo de Kruskal: o conjunto A é  uma floresta cujos vértices são todos os vértices do grafo e a aresta segura
-    //   adicionada é sempre uma aresta de peso mínimo no grafo que conecta duas componentes distintas.
-    pub fn get_mst_kruskal(&self) -> Self {
-        let mut a: HashSet<(usize, usize)> = HashSet::new();
-        let mut v_sets: Vec<HashSet<usize>> = Vec::new();
-        let mut heap: BinaryHeap<Reverse<(u64, (usize, usize))>> = BinaryHeap::new();
+    pub fn tsp_2_opt_approx(&self, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        self.check_complete()?;
 
-        // Criando a floresta de conjuntos
-        for i in 0..self.vertices.len() {
-            v_sets.push(HashSet::from([i]));
+        // Inverter um trecho muda o sentido dos arcos dele, o que so preserva o custo em matrizes simetricas
+        if !self.is_symmetric() {
+            return Err("Graph is not symmetric!");
         }
 
-        // Ordenando as arestas
-        for i in 0..self.adjacency_matrix.len() {
-            for j in 0..self.adjacency_matrix.len() {
-                if self.get_edge_weight(i, j).unwrap() != 0 {
-                    heap.push(Reverse((self.get_edge_weight(i, j).unwrap(), (i, j))));
-                }
-            }
-        }
+        let mut actual_slice: Vec<usize>;
+        let mut actual_route: Vec<usize> = (0..self.num_vertices()).collect();
+        let mut actual_cost: u64 = self.get_route_cost(&actual_route)?;
+        let mut best_route: Vec<usize> = (0..self.num_vertices()).collect();
+        let mut best_cost: u64 = actual_cost;
+        let mut counter: usize = 0;
 
-        for _ in 0..heap.len() {
-            // Remove a aresta de menor peso da heap
-            let (u, v) = match heap.pop() {
-                Some(Reverse((_, (u1, v1)))) => (u1, v1),
-                None => (0, 0)
-            };
-            let (mut set1_idx, mut set2_idx): (usize, usize) = (0, 0);
+        let start_time = Instant::now();
 
-            // Verifica em todos os sets se src ou dst estão inclusos neles
-            for i in 0..v_sets.len() {
-                if v_sets[i].contains(&u) {
-                    set1_idx = i;
-                }
+        while actual_cost == best_cost {
+            for i in 0..self.num_vertices() {
+                for j in i + 1..self.num_vertices() {
+                    actual_slice = actual_route[i..j].to_vec();
+                    actual_slice.reverse();
+                    actual_route.splice(i..j, actual_slice);
+                    
+                    actual_cost = self.get_route_cost(&actual_route)?;
 
-                if v_sets[i].contains(&v) {
-                    set2_idx = i;
+                    if actual_cost < best_cost {
+                        best_cost = actual_cost;
+                        best_route = actual_route.clone();
+                    }
                 }
             }
 
-            // Caso os dois não estejam no mesmo set
-            if set1_idx != set2_idx {
-                // Insere-se essa aresta
-                a.insert((u, v));
-                
-                // E junta os sets
-                let t = v_sets.remove(set2_idx);
-                
-                if set2_idx < set1_idx {
-                    v_sets[set1_idx-1].extend(&t);
-                }
-                else {
-                    v_sets[set1_idx].extend(&t);
-                }
+            if log && counter.is_multiple_of(10000) {
+                println!("Iteration: {} - Route cost: {} - Route found: {}", counter, best_cost, best_route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
             }
+            counter += 1;
         }
 
-        let mut adjacency_matrix: Vec<Vec<u64>> = vec![vec![0; self.adjacency_matrix.len()]; self.adjacency_matrix.len()];
-        for (src, dst) in a.into_iter() {
-            adjacency_matrix[src][dst] = self.get_edge_weight(src, dst).unwrap();
+        let end_time = Instant::now();
+        if log {
+            println!("Time elapsed: {} - Total iterations: {}", end_time.duration_since(start_time).as_micros(), counter);
         }
 
-        return Graph::from(self.vertices.clone(), adjacency_matrix);
+        Ok((best_route, best_cost))
     }
 
-    // Algoritmo de Prim: inicia adicionando ao conjunto A os vertices ligados pela aresta de menor custo
-    //      e após vai adicionando os vertices que tiverem menor custo e estejam sejam adjacentes aos já existentes
-    // https://pt.wikipedia.org/wiki/Algoritmo_de_Prim
-    pub fn get_mst_prim(&self) -> Self {
-        let mut a: HashSet<usize> = HashSet::with_capacity(self.vertices.len());
-        let mut heap: BinaryHeap<Reverse<(u64, (usize, usize))>> = BinaryHeap::new();
-        let mut edges: Vec<(usize, usize)> = Vec::with_capacity(self.vertices.len() - 1);
-
-        // Pega a aresta de menor valor diferente de zero
-        let mut min: (usize, usize) = (0, 0);
-        for i in 0..self.vertices.len() {
-            for j in 0..self.vertices.len() {
-                if self.get_edge_weight(i, j).unwrap() != 0 && self.get_edge_weight(i, j).unwrap() < self.get_edge_weight(min.0, min.1).unwrap() {
-                    min = (i, j);
-                }
-            }
-        }
-
-        // Adicionando a primeira aresta na heap
-        heap.push(Reverse((self.get_edge_weight(min.0, min.1).unwrap(), min)));
-        a.insert(min.0);
-        let adjacents = self.get_adjacent_vertices(min.0).unwrap();
+    // 3-opt sem inversoes a partir do vizinho mais proximo, alternando com or-opt ate um otimo local.
+    // Nenhum movimento inverte trechos, entao funciona em matrizes assimetricas.
+    pub fn tsp_3_opt_approx(&self, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        let start_time = Instant::now();
 
-        // Adicionando as arestas para adjacentes do primeiro vertice na heap ---
-        for adj_vertex in adjacents.into_iter() {
-            if a.contains(&adj_vertex) {
-                continue;
-            }
+        let mut route: Vec<usize> = self.tsp_nearest_neighbor_greedy()?;
+        let mut counter: usize = 0;
 
-            heap.push(Reverse((self.get_edge_weight(min.0, adj_vertex).unwrap(), (min.0, adj_vertex))));
+        if log {
+            println!("Nearest neighbor route cost: {}", self.get_route_cost(&route)?);
         }
 
-        // Itera ate que todos os vertices estejam acessiveis
         loop {
-            if a.len() == self.vertices.len() {
-                break;
-            }
+            counter += 1;
 
-            // Remove da heap
-            let (priority, src, dst) = match heap.pop() {
-                Some(Reverse((prio, (sr, ds)))) => (prio, sr, ds),
-                None => { continue; }
-            };
+            let segment_improved: bool = self.improve_segment_insertion(&mut route);
+            let or_opt_improved: bool = self.improve_or_opt(&mut route);
 
-            if a.contains(&dst) {
-                continue;
+            if !segment_improved && !or_opt_improved {
+                break;
             }
+        }
 
-            // Insere um vertice que nao havia sido explorado ainda
-            a.insert(dst);
-            // Adiciona a aresta no vetor de arestas
-            edges.push((src, dst));
-            // Pega os adjacentes
-            let adjacents = self.get_adjacent_vertices(dst).unwrap();
-
-            for adj_vertex in adjacents.into_iter() {
-                if a.contains(&adj_vertex) {
-                    continue;
-                }
+        let cost: u64 = self.get_route_cost(&route)?;
 
-                // Popula a heap denovo
-                heap.push(Reverse((self.get_edge_weight(dst, adj_vertex).unwrap(), (dst, adj_vertex))));
-            }
-        }
+        let end_time = Instant::now();
 
-        // Transforma tudo em um novo grafo :)
-        let mut adjacency_matrix: Vec<Vec<u64>> = vec![vec![0; self.adjacency_matrix.len()]; self.adjacency_matrix.len()];
-        for (src, dst) in edges.into_iter() {
-            adjacency_matrix[src][dst] = self.get_edge_weight(src, dst).unwrap();
+        if log {
+            println!("Time elapsed: {} - Total iterations: {} - Route cost: {} - Route found: {}", end_time.duration_since(start_time).as_micros(), counter, cost, route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
         }
 
-        return Graph::from(self.vertices.clone(), adjacency_matrix);
+        Ok((route, cost))
     }
 
-    pub fn get_mst_boruvka(&self) -> Self {
-        todo!()
-    }
+    // Or-opt a partir do vizinho mais proximo. Funciona em matrizes assimetricas.
+    pub fn tsp_or_opt_approx(&self, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        let start_time = Instant::now();
 
-    pub fn perfect_matching(&self) -> Result<Self, &'static str> {
-        let mut visited_vertices = vec![false; self.num_vertices()];
-        let mut max_coup_graph = Graph::new();
+        let mut route: Vec<usize> = self.tsp_nearest_neighbor_greedy()?;
 
-        for i in 0..self.num_vertices() {
-            if visited_vertices[i] {
-                continue;
-            }
+        if log {
+            println!("Nearest neighbor route cost: {}", self.get_route_cost(&route)?);
+        }
 
-            visited_vertices[i] = true;
-            let mut min_cost_vertex: usize = 0;
+        self.improve_or_opt(&mut route);
 
-            for j in 0..self.num_vertices() {
-                if visited_vertices[j] {
-                    continue;
-                }
+        let cost: u64 = self.get_route_cost(&route)?;
 
-                if self.get_edge_weight(i, j)? < min_cost {
-                    min_cost = j;
-                }
-            }
+        let end_time = Instant::now();
 
-            visited_vertices[min_cost_vertex] = true;
-            max_coup_graph.insert_vertex(self.get_vertex(i)?.clone());
-            max_coup_graph.insert_vertex(self.get_vertex(min_cost_vertex)?.clone());
-            max_coup_graph.insert_edge(i, min_cost_vertex, self.get_edge_weight(i, min_cost_vertex)?, false);
+        if log {
+            println!("Time elapsed: {} - Route cost: {} - Route found: {}", end_time.duration_since(start_time).as_micros(), cost, route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
         }
 
-        Ok(max_coup_graph)
+        Ok((route, cost))
     }
 
-    pub fn get_odd_degree_vertices(&self) -> Result<Vec<usize>, &'static str> {
-        let mut odd_degree_vertices: Vec<usize> = Vec::new();
+    // Algoritmo de Christofides: https://en.wikipedia.org/wiki/Christofides_algorithm
+    // MST + emparelhamento dos vertices de grau impar, circuito euleriano e atalhos nos vertices repetidos.
+    pub fn tsp_christofides_approx(&self, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        self.check_complete()?;
 
-        for i in 0..self.num_vertices() {
-            if self.get_adjacent_vertices(i)?.len() % 2 != 0 {
-                odd_degree_vertices.push(i);
-            }
+        if !self.is_symmetric() {
+            return Err("Graph is not symmetric!");
         }
 
-        Ok(odd_degree_vertices)
-    }
-
-    pub fn tsp_brute_force(&self, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
-        if !self.is_squared() {
-            return Err("Graph is not squared!");
+        if self.num_vertices() == 0 {
+            return Err("Graph is empty!");
         }
 
-        let first_route: Vec<usize> = (0..self.num_vertices()).collect();
-        let mut actual_cost: u64 = self.get_route_cost(&first_route)?;
-        let mut best_route: Vec<usize> = first_route.clone();
-        let mut best_cost: u64 = actual_cost.clone();
-        let mut counter: usize = 0;
-
         let start_time = Instant::now();
 
-        let permutations = first_route.iter().permutations(self.num_vertices() - 1);
+        let mst: SpanningTree = self.get_mst_prim_dense();
+        let odd_degree_vertices: Vec<usize> = mst.to_multigraph(self)?.get_odd_degree_vertices();
 
-        for permutation in permutations {
-            counter += 1;
-            permutation.push(&(num_vertices - 1));
-            actual_cost = self.get_route_cost_2(&permutation)?;
+        let mst_time = Instant::now();
 
-            if actual_cost < best_cost {
-                best_cost = actual_cost.clone();
-                best_route = permutation.into_iter().cloned().collect();
-            }
+        // O vertice k do subgrafo é o vertice odd_degree_vertices[k] do grafo original
+        let (odd_graph, _) = self.induced_subgraph(&odd_degree_vertices)?;
+        let matching_edges: Vec<(usize, usize, u64)> = odd_graph.perfect_matching()?.into_iter()
+            .map(|(u, v)| (odd_degree_vertices[u], odd_degree_vertices[v], odd_graph.weight(u, v)))
+            .collect();
 
-            if log && counter % 10000 == 0{
-                println!("Iteration: {} - Time elapsed: {} - Route cost: {}", counter, Instant::now().duration_since(start_time).as_micros(), best_cost);
+        // A MST junto com o emparelhamento pode repetir arestas, por isso o multigrafo
+        let mut multigraph: Multigraph<V> = mst.to_multigraph(self)?;
+
+        for (u, v, weight) in matching_edges {
+            multigraph.insert_edge(u, v, weight)?;
+        }
+
+        // Eulirian cycle
+        let eulerian_path: Vec<usize> = multigraph.get_eulerian_circuit(0)?;
+
+        // Atalhos: pula os vertices que ja apareceram no circuito
+        let mut visited_vertices: Vec<bool> = vec![false; self.num_vertices()];
+        let mut route: Vec<usize> = Vec::with_capacity(self.num_vertices());
+
+        for vertex_idx in eulerian_path {
+            if !visited_vertices[vertex_idx] {
+                visited_vertices[vertex_idx] = true;
+                route.push(vertex_idx);
             }
         }
-        
+
+        if route.len() != self.num_vertices() {
+            return Err("Graph is not connected!");
+        }
+
+        let cost: u64 = self.get_route_cost(&route)?;
+
         let end_time = Instant::now();
 
         if log {
-            println!("Total iterations: {} - Time elapsed: {} - Best route cost: {} - Best route found: {}", counter, end_time.duration_since(start_time).as_micros(), best_cost, best_route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
+            println!("MST time elapsed: {}", mst_time.duration_since(start_time).as_micros());
+            println!("Time elapsed: {} - Route cost: {} - Route found: {}", end_time.duration_since(start_time).as_micros(), cost, route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
         }
 
-        Ok((best_route, best_cost))
+        Ok((route, cost))
     }
 
-    pub fn tsp_2_opt_approx(&self, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
-        if !self.is_squared() {
-            return Err("Graph is not squared!");
+    // Vizinho mais proximo a partir do vertice 0: sempre segue o arco mais barato que sai do vertice atual
+    // para um vertice ainda nao visitado. Respeita a direcao dos arcos.
+    pub fn tsp_nearest_neighbor_greedy(&self) -> Result<Vec<usize>, &'static str> {
+        self.check_complete()?;
+
+        if self.num_vertices() == 0 {
+            return Err("Graph is empty!");
         }
 
-        let mut actual_slice: Vec<usize>;
-        let mut actual_route: Vec<usize> = (0..self.num_vertices()).collect();
-        let mut actual_cost: u64 = self.get_route_cost(&actual_route)?;
-        let mut best_route: Vec<usize> = (0..self.num_vertices()).collect();
-        let mut best_cost: u64 = actual_cost.clone();
-        let mut counter: usize = 0;
+        let mut visited_vertices: Vec<bool> = vec![false; self.num_vertices()];
+        let mut actual_vertex: usize = 0;
+        let mut actual_route: Vec<usize> = Vec::with_capacity(self.num_vertices());
+
+        // Generating starting point
+        visited_vertices[0] = true;
+        actual_route.push(0);
+
+        for _ in 1..self.num_vertices() {
+            let min_cost_vertex: usize = (0..self.num_vertices())
+                .filter(|&j| !visited_vertices[j])
+                .min_by_key(|&j| self.weight(actual_vertex, j))
+                .unwrap();
+
+            visited_vertices[min_cost_vertex] = true;
+            actual_vertex = min_cost_vertex;
+            actual_route.push(min_cost_vertex);
+        }
+
+        Ok(actual_route)
+    }
+
+    // Insercao mais barata: comeca com o vertice 0 e a cada passo insere o vertice e a posicao que menos
+    // aumentam a rota, w(a, v) + w(v, b) - w(a, b). Usa os arcos no sentido da rota, entao vale para ATSP.
+    pub fn tsp_cheapest_insertion(&self, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        self.check_complete()?;
+
+        if self.num_vertices() == 0 {
+            return Err("Graph is empty!");
+        }
 
         let start_time = Instant::now();
 
-        while actual_cost == best_cost {
-            for i in 0..self.num_vertices() {
-                for j in i + 1..self.num_vertices() {
-                    actual_slice = actual_route[i..j].to_vec();
-                    actual_slice.reverse();
-                    actual_route.splice(i..j, actual_slice);
-                    
-                    actual_cost = self.get_route_cost(&actual_route)?;
+        let n: usize = self.num_vertices();
+        let mut in_route: Vec<bool> = vec![false; n];
+        let mut route: Vec<usize> = Vec::with_capacity(n);
 
-                    if actual_cost < best_cost {
-                        best_cost = actual_cost.clone();
-                        best_route = actual_route.clone();
+        in_route[0] = true;
+        route.push(0);
+
+        for _ in 1..n {
+            // (aumento de custo, vertice, posicao onde ele entra)
+            let mut best: Option<(i64, usize, usize)> = None;
+
+            for v in (0..n).filter(|&v| !in_route[v]) {
+                for position in 0..route.len() {
+                    let a: usize = route[position];
+                    let b: usize = route[(position + 1) % route.len()];
+                    let increase: i64 = self.weight(a, v) as i64 + self.weight(v, b) as i64 - self.weight(a, b) as i64;
+
+                    if best.is_none_or(|(actual, _, _)| increase < actual) {
+                        best = Some((increase, v, position + 1));
                     }
                 }
             }
 
-            if log && counter % 10000 == 0{
-                println!("Iteration: {} - Route cost: {} - Route found: {}", counter, best_cost, best_route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
-            }
-            counter += 1;
+            let (_, v, position) = best.unwrap();
+            in_route[v] = true;
+            route.insert(position, v);
         }
 
+        let cost: u64 = self.get_route_cost(&route)?;
+
         let end_time = Instant::now();
+
         if log {
-            println!("Time elapsed: {} - Total iterations: {}", end_time.duration_since(start_time).as_micros(), counter);
+            println!("Time elapsed: {} - Route cost: {} - Route found: {}", end_time.duration_since(start_time).as_micros(), cost, route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
         }
 
-        Ok((best_route, best_cost))
-    }
-
-    pub fn tsp_3_opt_approx(&self, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
-        todo!()
+        Ok((route, cost))
     }
+}
 
-    pub fn tsp_christofides_approx(&self) -> Result<Vec<usize>, &'static str> {
-        if !self.is_squared() {
-            return Err("Graph is not squared!");
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinates::Metric;
 
-        if !self.is_symmetric() {
-            return Err("Graph is not symmetric!");
-        }
+    #[test]
+    fn from_indexes_labels() {
+        let t: Graph<&str> = Graph::from(vec!["a", "b"], vec![vec![0, 3], vec![3, 0]]);
 
-        let start_time = Instant::now();
+        assert_eq!(t.index_of(&"b"), Ok(1));
+        assert_eq!(t.get_edge_weight(0, 1), Ok(3));
+    }
 
-        let mut mst: Graph<V> = self.get_mst_prim();
-        let mut odd_degree_vertices: Vec<usize> = mst.get_odd_degree_vertices()?;
-        let mut odd_graph: Graph<V> = Graph::new();
+    #[test]
+    #[should_panic(expected = "Duplicate vertex labels!")]
+    fn from_rejects_duplicate_labels() {
+        Graph::from(vec![0, 1, 0], vec![vec![0; 3]; 3]);
+    }
 
-        for i in 0..odd_degree_vertices.len() {
-            for j in 0..odd_degree_vertices.len() {
-                odd_graph.insert_edge(odd_degree_vertices[i], odd_degree_vertices[j], self.get_edge_weight(odd_degree_vertices[i], odd_degree_vertices[j])?, false)?;
-            }
-        }
+    #[test]
+    fn try_from_rejects_duplicate_labels() {
+        assert!(Graph::try_from(vec!["a", "b", "a"], vec![vec![0; 3]; 3]).is_err());
+        assert_eq!(Graph::try_from(vec!["a", "b"], vec![vec![0, 3], vec![3, 0]]).map(|t| t.num_vertices()), Ok(2));
+    }
 
-        let mut matching: Graph<V> = odd_graph.perfect_matching()?;
-        mst.union(matching)?;
+    #[test]
+    fn from_coordinates_rejects_duplicate_labels() {
+        let coordinates: Coordinates = Coordinates::new(vec![(0.0, 0.0), (1.0, 0.0)], Metric::Euclidean);
 
-        // Eulirian cycle
-        let mut eulerian_path: Vec<usize> = mst.get_eulerian_path()?;
+        assert!(Graph::from_coordinates(vec![7, 7], coordinates).is_err());
+    }
 
-        let mut end_time = Instant::now();
+    #[test]
+    fn directed_flag_of_insert_and_remove_edge() {
+        let mut t: Graph<&str> = Graph::from(vec!["a", "b", "c"], vec![vec![0; 3]; 3]);
 
-        if log {
-            println!("MST time elapsed: {}", end_time.duration_since(start_time).as_micros());
-        }   
-    }
+        // directed: so src -> dst; sem directed: os dois sentidos
+        t.insert_edge(0, 1, 4, true).unwrap();
+        t.insert_edge_by_label(&"b", &"c", 6, false).unwrap();
+        assert_eq!(t.get_adjacency_matrix(), &vec![vec![0, 4, 0], vec![0, 0, 6], vec![0, 6, 0]]);
 
-    pub fn tsp_nearest_neighbor_greedy(&self) -> Result<Vec<usize>, &'static str> {
-        if !self.is_squared() {
-            return Err("Graph is not squared!");
-        }
+        t.remove_edge(2, 1, true).unwrap();
+        assert_eq!((t.get_edge_weight(1, 2), t.get_edge_weight(2, 1)), (Ok(6), Ok(0)));
 
-        let mut actual_vertex: usize = 0;
-        let mut actual_route: Vec<usize> = Vec::new();
-        let mut actual_cost: u64;
-        let mut min_cost: u64;
-        let mut min_cost_vertex: usize;
+        t.insert_edge(0, 2, 5, false).unwrap();
+        t.remove_edge_by_label(&"a", &"c", false).unwrap();
+        assert_eq!((t.get_edge_weight(0, 2), t.get_edge_weight(2, 0)), (Ok(0), Ok(0)));
 
-        // Generating starting point
-        actual_route.push(0);
+        assert!(t.insert_edge(0, 3, 1, true).is_err());
+        assert!(t.remove_edge(3, 0, false).is_err());
+    }
 
-        for i in 1..self.num_vertices() {
-            min_cost = self.get_edge_weight(actual_vertex, i)?;
-            min_cost_vertex = i;
+    #[test]
+    fn union_keeps_arc_direction() {
+        let mut t: Graph<&str> = Graph::from(vec!["a", "b"], vec![vec![0, 0], vec![0, 0]]);
+        let other: Graph<&str> = Graph::from(vec!["b", "c"], vec![vec![0, 2], vec![0, 0]]);
 
-            for j in 0..self.num_vertices() {
-                if actual_route.contains(&j) {
-                    continue;
-                }
+        assert_eq!(t.union(&other), Ok(vec![1, 2]));
+        assert_eq!((t.get_edge_weight(1, 2), t.get_edge_weight(2, 1)), (Ok(2), Ok(0)));
+    }
 
-                actual_cost = self.get_edge_weight(actual_vertex, j)?;
+    #[test]
+    fn coincident_points_are_still_linked() {
+        // Pontos a menos de 0.5 um do outro ficam com distancia 0 no EUC_2D
+        let coordinates: Coordinates = Coordinates::new(vec![(0.0, 0.0), (0.0, 0.0), (0.2, 0.1)], Metric::Euclidean);
+        let t: Graph<usize> = Graph::from_coordinates(vec![0, 1, 2], coordinates).unwrap();
+
+        assert_eq!(t.get_edge_weight(0, 1), Ok(0));
+        assert_eq!(t.neighbors(0).unwrap().collect::<Vec<(usize, u64)>>(), vec![(1, 0), (2, 0)]);
+        assert_eq!(t.edges().count(), 6);
+        assert_eq!(t.undirected_edges().count(), 3);
+        assert!(t.is_strongly_connected());
+        assert!(t.complement(1).edges().next().is_none());
+    }
 
-                if actual_cost < min_cost {
-                    min_cost = actual_cost;
-                    min_cost_vertex = j;
-                }
-            }
+    #[test]
+    fn check_complete_rejects_missing_arcs() {
+        let complete: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0, 1, 2], vec![1, 0, 3], vec![2, 3, 0]]);
+        // Pesos zero fora da diagonal desconectam o grafo
+        let disconnected: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0, 1, 0], vec![1, 0, 0], vec![0, 0, 0]]);
+        // Fortemente conexo, mas so num sentido
+        let one_way: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0, 1, 0], vec![0, 0, 1], vec![1, 0, 0]]);
+        let ragged: Graph<usize> = Graph::from(vec![0, 1], vec![vec![0, 1], vec![1]]);
+        let coordinates: Coordinates = Coordinates::new(vec![(0.0, 0.0), (0.0, 0.0)], Metric::Euclidean);
+
+        assert_eq!(complete.check_complete(), Ok(()));
+        assert_eq!(Graph::from(vec![0], vec![vec![0]]).check_complete(), Ok(()));
+        assert_eq!(disconnected.check_complete(), Err("Graph is not complete!"));
+        assert_eq!(one_way.check_complete(), Err("Graph is not complete!"));
+        assert_eq!(ragged.check_complete(), Err("Graph is not squared!"));
+        assert_eq!(Graph::from_coordinates(vec![0, 1], coordinates).unwrap().check_complete(), Ok(()));
+    }
 
-            actual_vertex = min_cost_vertex.clone();
-            actual_route.push(min_cost_vertex);
-        }
+    #[test]
+    fn zero_weight_means_no_edge_in_matrix() {
+        let t: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0, 1, 0], vec![1, 0, 0], vec![0, 0, 0]]);
 
-        todo!()
+        assert_eq!(t.undirected_edges().count(), 1);
+        assert!(!t.is_connected());
+        assert_eq!(t.complement(5).undirected_edges().count(), 2);
     }
-}
\ No newline at end of file
+}
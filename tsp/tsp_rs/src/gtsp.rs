@@ -0,0 +1,310 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::time::Instant;
+use crate::graph::Graph;
+
+// Particao dos vertices em grupos (clusters) para o TSP generalizado: a rota visita exatamente um vertice de
+// cada grupo. `cluster_of[v]` é o grupo do vertice v e `members[c]` os vertices do grupo c em ordem crescente.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clusters {
+    members: Vec<Vec<usize>>,
+    cluster_of: Vec<usize>
+}
+
+impl Clusters {
+    // Monta os grupos a partir do rotulo de cada vertice. Os rotulos nao precisam ser consecutivos: os grupos
+    // sao numerados na ordem crescente dos rotulos.
+    pub fn from_labels(labels: &[u64]) -> Result<Self, &'static str> {
+        if labels.is_empty() {
+            return Err("There must be at least one cluster!");
+        }
+
+        let mut distinct: Vec<u64> = labels.to_vec();
+        distinct.sort();
+        distinct.dedup();
+
+        let cluster_of: Vec<usize> = labels.iter().map(|label| distinct.binary_search(label).unwrap()).collect();
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); distinct.len()];
+
+        for (v, &c) in cluster_of.iter().enumerate() {
+            members[c].push(v);
+        }
+
+        Ok(Clusters {
+            members,
+            cluster_of
+        })
+    }
+
+    pub fn num_clusters(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn get_members(&self, cluster: usize) -> &Vec<usize> {
+        &self.members[cluster]
+    }
+
+    pub fn get_cluster_of(&self, vertex: usize) -> usize {
+        self.cluster_of[vertex]
+    }
+
+    // Se a rota visita exatamente um vertice de cada grupo
+    pub fn accepts(&self, route: &[usize]) -> bool {
+        let mut visited: Vec<bool> = vec![false; self.num_clusters()];
+
+        route.len() == self.num_clusters()
+            && route.iter().all(|&v| v < self.cluster_of.len() && !std::mem::replace(&mut visited[self.cluster_of[v]], true))
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Verificacoes comuns das funcoes do TSP generalizado
+    pub(crate) fn check_clustered_instance(&self, clusters: &Clusters) -> Result<(), &'static str> {
+        self.check_complete()?;
+
+        if clusters.cluster_of.len() != self.num_vertices() {
+            return Err("There must be one cluster per vertex!");
+        }
+
+        Ok(())
+    }
+
+    // Escolha dos vertices para uma ordem fixa dos grupos: caminho mais curto no grafo em camadas que sai de um
+    // vertice do primeiro grupo, passa por um vertice de cada grupo na ordem e volta ao vertice de saida.
+    // Comeca pelo menor grupo, entao custa O(|menor grupo|·soma de |Ci|·|Ci+1|). Retorna se a rota melhorou.
+    fn improve_cluster_choice(&self, clusters: &Clusters, route: &mut [usize]) -> bool {
+        let m: usize = route.len();
+
+        if m < 2 {
+            return false;
+        }
+
+        let order: Vec<usize> = route.iter().map(|&v| clusters.cluster_of[v]).collect();
+        let first: usize = (0..m).min_by_key(|&i| clusters.members[order[i]].len()).unwrap();
+        let layers: Vec<&Vec<usize>> = (0..m).map(|k| &clusters.members[order[(first + k) % m]]).collect();
+
+        let mut best: Option<(u64, Vec<usize>)> = None;
+
+        for &start in layers[0].iter() {
+            // dist[k][i]: menor custo de start ate o vertice i da camada k; parent guarda o indice na camada anterior
+            let mut dist: Vec<u64> = vec![0];
+            let mut parents: Vec<Vec<usize>> = Vec::with_capacity(m);
+            let mut previous: Vec<usize> = vec![start];
+
+            for layer in layers.iter().skip(1) {
+                let mut next_dist: Vec<u64> = Vec::with_capacity(layer.len());
+                let mut parent: Vec<usize> = Vec::with_capacity(layer.len());
+
+                for &v in layer.iter() {
+                    let (d, p) = (0..previous.len()).map(|i| (dist[i] + self.weight(previous[i], v), i)).min().unwrap();
+                    next_dist.push(d);
+                    parent.push(p);
+                }
+
+                dist = next_dist;
+                parents.push(parent);
+                previous = layer.to_vec();
+            }
+
+            let (cost, mut index) = (0..previous.len()).map(|i| (dist[i] + self.weight(previous[i], start), i)).min().unwrap();
+
+            if best.as_ref().is_none_or(|(actual, _)| cost < *actual) {
+                let mut chosen: Vec<usize> = vec![0; m];
+                chosen[0] = start;
+
+                for k in (1..m).rev() {
+                    chosen[k] = layers[k][index];
+                    index = parents[k - 1][index];
+                }
+
+                best = Some((cost, chosen));
+            }
+        }
+
+        let (cost, chosen) = best.unwrap();
+
+        if cost < self.get_route_cost(route).unwrap() {
+            for (k, v) in chosen.into_iter().enumerate() {
+                route[(first + k) % m] = v;
+            }
+
+            return true;
+        }
+
+        false
+    }
+
+    // Tira o grupo da posicao i e o reinsere com o vertice e a posicao mais baratos, o que muda ao mesmo tempo a
+    // ordem dos grupos e o vertice escolhido. Aplica a primeira melhora encontrada; retorna se a rota mudou.
+    fn improve_cluster_relocation(&self, clusters: &Clusters, route: &mut Vec<usize>) -> bool {
+        let m: usize = route.len();
+        let w = |i: usize, j: usize| -> i64 { self.weight(i, j) as i64 };
+
+        if m < 3 {
+            return false;
+        }
+
+        for i in 0..m {
+            let (prev, current, next) = (route[(i + m - 1) % m], route[i], route[(i + 1) % m]);
+            let removal_gain: i64 = w(prev, current) + w(current, next) - w(prev, next);
+
+            let mut rest: Vec<usize> = route.clone();
+            rest.remove(i);
+
+            let (cost, v, position) = clusters.members[clusters.cluster_of[current]].iter()
+                .flat_map(|&v| (0..rest.len()).map(move |p| (v, p)))
+                .map(|(v, p)| {
+                    let (a, b) = (rest[p], rest[(p + 1) % rest.len()]);
+                    (w(a, v) + w(v, b) - w(a, b), v, p + 1)
+                })
+                .min()
+                .unwrap();
+
+            if cost < removal_gain {
+                rest.insert(position, v);
+                *route = rest;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    // TSP generalizado: visita exatamente um vertice de cada grupo com o menor custo. Constroi a rota por
+    // insercao mais barata de um vertice de um grupo ainda nao visitado, partindo do menor grupo, e depois
+    // alterna ate um otimo local: a melhor escolha de vertices para a ordem atual dos grupos, 3-opt sem
+    // inversao e or-opt sobre a ordem, e a realocacao de um grupo trocando o vertice. Aceita matrizes
+    // assimetricas.
+    pub fn gtsp_approx(&self, clusters: &Clusters, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        self.check_clustered_instance(clusters)?;
+
+        let start_time = Instant::now();
+
+        let m: usize = clusters.num_clusters();
+        let w = |i: usize, j: usize| -> i64 { self.weight(i, j) as i64 };
+        let smallest: usize = (0..m).min_by_key(|&c| clusters.members[c].len()).unwrap();
+
+        let mut route: Vec<usize> = vec![clusters.members[smallest][0]];
+        let mut visited: Vec<bool> = (0..m).map(|c| c == smallest).collect();
+
+        while route.len() < m {
+            let (_, v, position) = (0..self.num_vertices())
+                .filter(|&v| !visited[clusters.cluster_of[v]])
+                .flat_map(|v| (0..route.len()).map(move |p| (v, p)))
+                .map(|(v, p)| {
+                    let (a, b) = (route[p], route[(p + 1) % route.len()]);
+                    (w(a, v) + w(v, b) - w(a, b), v, p + 1)
+                })
+                .min()
+                .unwrap();
+
+            route.insert(position, v);
+            visited[clusters.cluster_of[v]] = true;
+        }
+
+        if log {
+            println!("Insertion route cost: {}", self.get_route_cost(&route)?);
+        }
+
+        let mut counter: usize = 0;
+
+        loop {
+            counter += 1;
+
+            let mut improved: bool = self.improve_cluster_choice(clusters, &mut route);
+            improved |= self.improve_segment_insertion(&mut route);
+            improved |= self.improve_or_opt(&mut route);
+            improved |= self.improve_cluster_relocation(clusters, &mut route);
+
+            if !improved {
+                break;
+            }
+        }
+
+        let cost: u64 = self.get_route_cost(&route)?;
+
+        let end_time = Instant::now();
+
+        if log {
+            println!("Time elapsed: {} - Total iterations: {} - Route cost: {} - Route found: {}", end_time.duration_since(start_time).as_micros(), counter, cost, route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
+        }
+
+        Ok((route, cost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{brute_force_gtsp, next_random, random_graph};
+    use itertools::Itertools;
+
+    #[test]
+    fn clusters_from_labels() {
+        let clusters: Clusters = Clusters::from_labels(&[7, 3, 7, 10, 3]).unwrap();
+
+        assert_eq!(clusters.num_clusters(), 3);
+        assert_eq!(clusters.get_members(0), &vec![1, 4]);
+        assert_eq!(clusters.get_members(1), &vec![0, 2]);
+        assert_eq!(clusters.get_members(2), &vec![3]);
+        assert_eq!((0..5).map(|v| clusters.get_cluster_of(v)).collect::<Vec<usize>>(), vec![1, 0, 1, 2, 0]);
+
+        assert!(clusters.accepts(&[3, 0, 4]));
+        assert!(!clusters.accepts(&[3, 0]));
+        assert!(!clusters.accepts(&[3, 0, 2]));
+        assert!(!clusters.accepts(&[3, 0, 5]));
+
+        assert!(Clusters::from_labels(&[]).is_err());
+    }
+
+    #[test]
+    fn gtsp_known_answer() {
+        // Grupos {0, 1}, {2, 3} e {4}: a rota barata usa 1 e 3
+        let t: Graph<usize> = Graph::from((0..5).collect(), vec![
+            vec![0, 1, 9, 9, 9],
+            vec![1, 0, 9, 2, 3],
+            vec![9, 9, 0, 1, 9],
+            vec![9, 2, 1, 0, 2],
+            vec![9, 3, 9, 2, 0]
+        ]);
+        let clusters: Clusters = Clusters::from_labels(&[0, 0, 1, 1, 2]).unwrap();
+        let (route, cost) = t.gtsp_approx(&clusters, false).unwrap();
+
+        assert!(clusters.accepts(&route));
+        assert_eq!(route.iter().copied().sorted().collect::<Vec<usize>>(), vec![1, 3, 4]);
+        assert_eq!(cost, 7);
+    }
+
+    #[test]
+    fn gtsp_is_never_better_than_brute_force() {
+        let mut seed: u64 = 51;
+
+        for n in [2, 4, 6, 8] {
+            for symmetric in [true, false] {
+                let t: Graph<usize> = random_graph(n, symmetric, &mut seed);
+
+                for groups in [1, 2, n / 2 + 1, n] {
+                    let labels: Vec<u64> = (0..n).map(|v| if v < groups { v as u64 } else { next_random(&mut seed) % groups as u64 }).collect();
+                    let clusters: Clusters = Clusters::from_labels(&labels).unwrap();
+                    let (route, cost) = t.gtsp_approx(&clusters, false).unwrap();
+
+                    assert!(clusters.accepts(&route));
+                    assert_eq!(t.get_route_cost(&route), Ok(cost));
+                    assert!(cost >= brute_force_gtsp(&t, &clusters));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn small_and_invalid_instances() {
+        let single: Graph<usize> = Graph::from(vec![0], vec![vec![0]]);
+        let pair: Graph<usize> = Graph::from(vec![0, 1], vec![vec![0, 3], vec![7, 0]]);
+
+        assert_eq!(single.gtsp_approx(&Clusters::from_labels(&[0]).unwrap(), false), Ok((vec![0], 0)));
+        assert_eq!(pair.gtsp_approx(&Clusters::from_labels(&[5, 5]).unwrap(), false).map(|(route, cost)| (route.len(), cost)), Ok((1, 0)));
+        assert_eq!(pair.gtsp_approx(&Clusters::from_labels(&[1, 2]).unwrap(), false).map(|(_, cost)| cost), Ok(10));
+
+        assert!(pair.gtsp_approx(&Clusters::from_labels(&[1]).unwrap(), false).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use crate::graph::Graph;
+
+// Movimentos de busca local que nunca invertem um trecho da rota. Cada arco continua sendo percorrido no
+// mesmo sentido, entao o ganho calculado vale tambem para matrizes assimetricas (ruas de mao unica).
+// As rotas sao fechadas: o ultimo vertice volta para o primeiro. As versoes `_with` so aplicam os movimentos
+// cuja rota resultante é aceita por `accept`, para restricoes como precedencia.
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Or-opt: tira um segmento de 1 a 3 vertices consecutivos e o reinsere, na mesma ordem, entre outros
+    // dois vertices. Aplica a primeira melhora encontrada ate nao haver mais; retorna se a rota mudou.
+    pub fn improve_or_opt(&self, route: &mut Vec<usize>) -> bool {
+        self.improve_or_opt_with(route, |_| true)
+    }
+
+    pub fn improve_or_opt_with<F: Fn(&[usize]) -> bool>(&self, route: &mut Vec<usize>, accept: F) -> bool {
+        let n: usize = route.len();
+        let w = |i: usize, j: usize| -> i64 { self.weight(i, j) as i64 };
+        let mut improved: bool = false;
+
+        'search: loop {
+            for segment_len in 1..=3 {
+                if n < segment_len + 2 {
+                    break;
+                }
+
+                for i in 0..=n - segment_len {
+                    let prev: usize = route[(i + n - 1) % n];
+                    let first: usize = route[i];
+                    let last: usize = route[i + segment_len - 1];
+                    let next: usize = route[(i + segment_len) % n];
+                    let removal_gain: i64 = w(prev, first) + w(last, next) - w(prev, next);
+
+                    for j in 0..n {
+                        // Pula os arcos que tocam o segmento: de route[i - 1] ate route[i + segment_len - 1]
+                        if (j + n + 1 - i) % n <= segment_len {
+                            continue;
+                        }
+
+                        let (a, b) = (route[j], route[(j + 1) % n]);
+
+                        if w(a, first) + w(last, b) - w(a, b) < removal_gain {
+                            let mut candidate: Vec<usize> = route.clone();
+                            let segment: Vec<usize> = candidate.drain(i..i + segment_len).collect();
+                            let position: usize = candidate.iter().position(|&v| v == a).unwrap() + 1;
+                            candidate.splice(position..position, segment);
+
+                            if accept(&candidate) {
+                                *route = candidate;
+                                improved = true;
+                                continue 'search;
+                            }
+                        }
+                    }
+                }
+            }
+
+            return improved;
+        }
+    }
+
+    // 3-opt sem inversao ("segment insertion"): remove os arcos depois de route[i], route[j] e route[k] e troca
+    // de lugar os trechos route[i+1..=j] e route[j+1..=k]. É a unica reconexao do 3-opt que mantem o sentido
+    // de todos os trechos. Aplica a primeira melhora encontrada ate nao haver mais; retorna se a rota mudou.
+    pub fn improve_segment_insertion(&self, route: &mut [usize]) -> bool {
+        self.improve_segment_insertion_with(route, |_| true)
+    }
+
+    pub fn improve_segment_insertion_with<F: Fn(&[usize]) -> bool>(&self, route: &mut [usize], accept: F) -> bool {
+        let n: usize = route.len();
+        let w = |i: usize, j: usize| -> i64 { self.weight(i, j) as i64 };
+        let mut improved: bool = false;
+
+        if n < 4 {
+            return false;
+        }
+
+        'search: loop {
+            for i in 0..n - 2 {
+                for j in i + 1..n - 1 {
+                    for k in j + 1..n {
+                        let (a, a_next) = (route[i], route[i + 1]);
+                        let (b, b_next) = (route[j], route[j + 1]);
+                        let (c, c_next) = (route[k], route[(k + 1) % n]);
+
+                        let delta: i64 = w(a, b_next) + w(c, a_next) + w(b, c_next) - w(a, a_next) - w(b, b_next) - w(c, c_next);
+
+                        if delta < 0 {
+                            route[i + 1..=k].rotate_left(j - i);
+
+                            if accept(route) {
+                                improved = true;
+                                continue 'search;
+                            }
+
+                            route[i + 1..=k].rotate_right(j - i);
+                        }
+                    }
+                }
+            }
+
+            return improved;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Cidades numa linha: a rota otima vai e volta pela linha, custo 2 * (n - 1)
+    fn line_graph(n: usize) -> Graph<usize> {
+        let matrix: Vec<Vec<u64>> = (0..n).map(|i| (0..n).map(|j| i.abs_diff(j) as u64).collect()).collect();
+
+        Graph::from((0..n).collect(), matrix)
+    }
+
+    #[test]
+    fn moves_fix_a_shuffled_line() {
+        let t: Graph<usize> = line_graph(6);
+        let mut route: Vec<usize> = vec![0, 3, 1, 4, 2, 5];
+
+        while t.improve_segment_insertion(&mut route) | t.improve_or_opt(&mut route) {}
+
+        assert_eq!(t.get_route_cost(&route), Ok(10));
+    }
+
+    #[test]
+    fn accept_filters_moves() {
+        let t: Graph<usize> = line_graph(5);
+        let original: Vec<usize> = vec![0, 2, 1, 3, 4];
+        let mut route: Vec<usize> = original.clone();
+
+        assert!(!t.improve_or_opt_with(&mut route, |_| false));
+        assert!(!t.improve_segment_insertion_with(&mut route, |_| false));
+        assert_eq!(route, original);
+    }
+
+    #[test]
+    fn short_routes_are_left_alone() {
+        let t: Graph<usize> = line_graph(2);
+        let mut single: Vec<usize> = vec![0];
+        let mut pair: Vec<usize> = vec![1, 0];
+
+        assert!(!t.improve_or_opt(&mut single));
+        assert!(!t.improve_or_opt(&mut pair));
+        assert!(!t.improve_segment_insertion(&mut pair));
+        assert_eq!(pair, vec![1, 0]);
+    }
+}
@@ -1,59 +1,451 @@
 use std::env;
 use std::process;
 use crate::graph::Graph;
+use crate::gtsp::Clusters;
+use crate::objective::Objective;
+use crate::path::RouteMode;
+use crate::precedence::Precedences;
+use crate::prize::PrizeRoute;
+use crate::solver::Solver;
+use crate::time_windows::{Schedule, TimeWindow};
+use crate::vrp::{FleetObjective, VehicleRoute};
 
 pub mod utils;
 pub mod graph;
+pub mod edge;
+pub mod multigraph;
+pub mod coordinates;
+pub mod union_find;
+pub mod mst;
+pub mod signed_graph;
+pub mod shortest_paths;
+pub mod connectivity;
+pub mod flow;
+pub mod assignment;
+pub mod matching;
+pub mod local_search;
+pub mod solver;
+pub mod transform;
+pub mod path;
+pub mod time_windows;
+pub mod vrp;
+pub mod mtsp;
+pub mod prize;
+pub mod gtsp;
+pub mod precedence;
+pub mod objective;
+#[cfg(test)]
+mod test_utils;
 
 fn main() {
     let mut args: Vec<String> = env::args().collect();
 
     if args.len() < 3 {
-        println!("Usage: {} <filename> <algorithm>", args[0]);
-        println!("    <filename> - TSP file to read");
-        println!("    <algorithm> - Algorithm to use (exact, 2opt)");
+        println!("Usage: {} <filename> <algorithm> [--path] [--start <vertex>] [--end <vertex>] [--windows <file>] [--demands <file> --capacity <n>] [--depot <vertex>] [--salesmen <m>] [--objective sum|max] [--prizes <file>] [--budget <n>] [--clusters <file>] [--via <algorithm>] [--precedences <file>] [--cost <objective>]", args[0]);
+        println!("    <filename> - TSP file to read (adjacency matrix, or TSPLIB coordinates if it ends with .tsp)");
+        println!("    <algorithm> - Algorithm to use ({}), time-windows, cvrp, mtsp, pctsp, orienteering, gtsp, precedence, or inspect to print a report of the graph", Solver::ALL.iter().map(|s| s.to_string()).collect::<Vec<String>>().join(", "));
+        println!("    --path - Open path that does not return to the first vertex (implied by --start and --end)");
+        println!("    --start, --end - Index of the first and last vertex of the path");
+        println!("    --windows - Time windows file for time-windows (one '<earliest> <latest> <service>' line per vertex)");
+        println!("    --demands, --capacity - Demands file (one per vertex) and vehicle capacity for cvrp");
+        println!("    --depot - Index of the depot for cvrp, mtsp, pctsp, orienteering and precedence (default 0)");
+        println!("    --prizes - Prize of each vertex (one per line) for orienteering, or penalty for skipping it for pctsp");
+        println!("    --budget - Maximum route length for orienteering");
+        println!("    --clusters - Cluster of each vertex (one label per line) for gtsp, which visits one vertex per cluster");
+        println!("    --via - Solve gtsp with this algorithm on the Noon-Bean transformation instead of the direct heuristic");
+        println!("    --precedences - Constraints for precedence, one '<before> <after>' line each (pickup before delivery)");
+        println!("    --cost - What the algorithm optimizes ({}, default length)", Objective::ALL.iter().map(|o| o.to_string()).collect::<Vec<String>>().join(", "));
+        println!("    --salesmen, --objective - Number of salesmen for mtsp and whether to minimize the total distance (sum, default) or the longest route (max)");
         process::exit(1);
     }
 
     let filename = args.remove(1);
     let algorithm = args.remove(1);
+    let options: Options = match parse_options(&args[1..]) {
+        Ok(o) => o,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
 
-    let t: Graph<usize> = utils::read_tsp_file(&filename).unwrap();
+    // Arquivos .tsp seguem o formato TSPLIB com coordenadas, os demais sao matrizes de adjacencia
+    let graph: std::io::Result<Graph<usize>> = if filename.ends_with(".tsp") {
+        utils::read_tsplib_coordinates_file(&filename)
+    } else {
+        utils::read_tsp_file(&filename)
+    };
+
+    let t: Graph<usize> = match graph {
+        Ok(t) => t,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
 
     let _results: (Vec<usize>, u64);
 
-    if algorithm == "exact" {
-        _results = match t.tsp_brute_force(true) {
+    if algorithm == "inspect" {
+        if let Err(e) = inspect(&t) {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    } else if let Some(solver) = Solver::from_name(&algorithm) {
+        let result: Result<(Vec<usize>, u64), &'static str> = match (options.cost, options.mode) {
+            (Objective::Length, mode) => t.solve_with_mode(solver, mode, true),
+            (cost, RouteMode::Cycle) => t.solve_with_objective(solver, cost, true),
+            _ => Err("Open paths only support the length objective!")
+        };
+
+        _results = match result {
             Ok(r) => r,
             Err(e) => {
                 println!("Error: {}", e);
                 process::exit(1);
             }
         };
-    } else if algorithm == "2opt" {
-        _results = match t.tsp_2_opt_approx(true) {
-            Ok(r) => r,
+    } else if algorithm == "time-windows" {
+        let windows: Vec<TimeWindow> = match &options.windows {
+            Some(windows_file) => match utils::read_time_windows_file(windows_file) {
+                Ok(w) => w,
+                Err(e) => {
+                    println!("Error: {}", e);
+                    process::exit(1);
+                }
+            },
+            None => {
+                println!("Error: time-windows expects --windows <file>");
+                process::exit(1);
+            }
+        };
+
+        match t.tsp_time_windows(&windows, LATENESS_PENALTY, true) {
+            Ok((route, schedule)) => print_schedule(&route, &schedule, &windows),
+            Err(e) => {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        };
+    } else if algorithm == "cvrp" {
+        let (demands, capacity): (Vec<u64>, u64) = match (&options.demands, options.capacity) {
+            (Some(demands_file), Some(capacity)) => match utils::read_vertex_values_file(demands_file) {
+                Ok(d) => (d, capacity),
+                Err(e) => {
+                    println!("Error: {}", e);
+                    process::exit(1);
+                }
+            },
+            _ => {
+                println!("Error: cvrp expects --demands <file> and --capacity <n>");
+                process::exit(1);
+            }
+        };
+
+        match t.cvrp_approx(options.depot, &demands, capacity, true) {
+            Ok(routes) => print_vehicle_routes(options.depot, &routes),
+            Err(e) => {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        };
+    } else if algorithm == "mtsp" {
+        let salesmen: usize = match options.salesmen {
+            Some(m) => m,
+            None => {
+                println!("Error: mtsp expects --salesmen <m>");
+                process::exit(1);
+            }
+        };
+
+        match t.mtsp_approx(options.depot, salesmen, options.objective, true) {
+            Ok(routes) => {
+                for (i, route) in routes.iter().enumerate() {
+                    let stops: Vec<String> = [options.depot].iter().chain(route.iter()).chain([options.depot].iter()).map(|v| v.to_string()).collect();
+                    println!("Salesman {}: {} - Cost: {}", i + 1, stops.join(" -> "), t.get_vehicle_route_cost(options.depot, route));
+                }
+            },
+            Err(e) => {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        };
+    } else if algorithm == "pctsp" || algorithm == "orienteering" {
+        let prizes: Vec<u64> = match &options.prizes {
+            Some(prizes_file) => match utils::read_vertex_values_file(prizes_file) {
+                Ok(p) => p,
+                Err(e) => {
+                    println!("Error: {}", e);
+                    process::exit(1);
+                }
+            },
+            None => {
+                println!("Error: {} expects --prizes <file>", algorithm);
+                process::exit(1);
+            }
+        };
+
+        let result: Result<PrizeRoute, &'static str> = if algorithm == "pctsp" {
+            t.pctsp_approx(options.depot, &prizes, true)
+        } else {
+            match options.budget {
+                Some(budget) => t.orienteering_approx(options.depot, &prizes, budget, true),
+                None => Err("orienteering expects --budget <n>")
+            }
+        };
+
+        match result {
+            Ok(prize_route) => {
+                println!("Visited: {} - Prize collected: {} - Route length: {}", prize_route.get_route().len(), prize_route.get_collected(), prize_route.get_length());
+                println!("Skipped: {} - Total: {}", prize_route.get_skipped().iter().map(|v| v.to_string()).collect::<Vec<String>>().join(", "), prize_route.get_skipped_total());
+            },
             Err(e) => {
                 println!("Error: {}", e);
                 process::exit(1);
             }
         };
-    } else if algorithm == "3opt" {
+    } else if algorithm == "gtsp" {
+        let clusters: Clusters = match &options.clusters {
+            Some(clusters_file) => match utils::read_vertex_values_file(clusters_file) {
+                Ok(labels) => match Clusters::from_labels(&labels) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        println!("Error: {}", e);
+                        process::exit(1);
+                    }
+                },
+                Err(e) => {
+                    println!("Error: {}", e);
+                    process::exit(1);
+                }
+            },
+            None => {
+                println!("Error: gtsp expects --clusters <file>");
+                process::exit(1);
+            }
+        };
 
-    } else if algorithm == "nn" {
+        let result: Result<(Vec<usize>, u64), &'static str> = match options.via {
+            Some(solver) => t.gtsp_noon_bean(&clusters, solver, true),
+            None => t.gtsp_approx(&clusters, true)
+        };
 
-    } else if algorithm == "christofides" {
-        _results = match t.tsp_christofides_approx(true) {
+        _results = match result {
             Ok(r) => r,
             Err(e) => {
                 println!("Error: {}", e);
                 process::exit(1);
             }
         };
-    } else if algorithm == "held-karp" {
+    } else if algorithm == "precedence" {
+        let precedences: Precedences = match &options.precedences {
+            Some(precedences_file) => match utils::read_precedences_file(precedences_file) {
+                Ok(p) => p,
+                Err(e) => {
+                    println!("Error: {}", e);
+                    process::exit(1);
+                }
+            },
+            None => {
+                println!("Error: precedence expects --precedences <file>");
+                process::exit(1);
+            }
+        };
+
+        _results = match t.tsp_precedence(options.depot, &precedences, true) {
+            Ok(r) => r,
+            Err(e) => {
+                println!("Error: {}", e);
 
+                if let Ok(Some(cycle)) = precedences.get_cycle(t.num_vertices()) {
+                    println!("Cycle: {} -> {}", cycle.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(" -> "), cycle[0]);
+                }
+
+                process::exit(1);
+            }
+        };
     } else {
         println!("Error: Unknown algorithm {}", algorithm);
         process::exit(1);
     }
-}
\ No newline at end of file
+}
+
+// Penalidade por unidade de atraso usada pelo time-windows: alta o bastante para preferir rotas sem atraso
+const LATENESS_PENALTY: u64 = 1000;
+
+// Opcoes de linha de comando depois do algoritmo
+struct Options {
+    mode: RouteMode,
+    windows: Option<String>,
+    demands: Option<String>,
+    capacity: Option<u64>,
+    depot: usize,
+    salesmen: Option<usize>,
+    objective: FleetObjective,
+    prizes: Option<String>,
+    budget: Option<u64>,
+    clusters: Option<String>,
+    via: Option<Solver>,
+    precedences: Option<String>,
+    cost: Objective
+}
+
+// Le as opcoes de linha de comando. Sem --path, --start ou --end a rota é um ciclo.
+fn parse_options(arguments: &[String]) -> Result<Options, String> {
+    let mut is_path: bool = false;
+    let mut start: Option<usize> = None;
+    let mut end: Option<usize> = None;
+    let mut windows: Option<String> = None;
+    let mut demands: Option<String> = None;
+    let mut capacity: Option<u64> = None;
+    let mut depot: usize = 0;
+    let mut salesmen: Option<usize> = None;
+    let mut objective: FleetObjective = FleetObjective::MinSum;
+    let mut prizes: Option<String> = None;
+    let mut budget: Option<u64> = None;
+    let mut clusters: Option<String> = None;
+    let mut via: Option<Solver> = None;
+    let mut precedences: Option<String> = None;
+    let mut cost: Objective = Objective::Length;
+    let mut arguments = arguments.iter();
+
+    while let Some(option) = arguments.next() {
+        match option.as_str() {
+            "--path" => is_path = true,
+            "--start" | "--end" => {
+                let vertex: usize = match arguments.next().map(|v| v.parse::<usize>()) {
+                    Some(Ok(v)) => v,
+                    _ => return Err(format!("{} expects a vertex index", option))
+                };
+
+                if option == "--start" {
+                    start = Some(vertex);
+                } else {
+                    end = Some(vertex);
+                }
+
+                is_path = true;
+            },
+            "--windows" | "--demands" | "--prizes" | "--clusters" | "--precedences" => {
+                let file: String = match arguments.next() {
+                    Some(file) => file.clone(),
+                    None => return Err(format!("{} expects a file", option))
+                };
+
+                match option.as_str() {
+                    "--windows" => windows = Some(file),
+                    "--demands" => demands = Some(file),
+                    "--prizes" => prizes = Some(file),
+                    "--clusters" => clusters = Some(file),
+                    _ => precedences = Some(file)
+                }
+            },
+            "--capacity" | "--budget" => {
+                let value: u64 = match arguments.next().map(|c| c.parse::<u64>()) {
+                    Some(Ok(c)) => c,
+                    _ => return Err(format!("{} expects a number", option))
+                };
+
+                if option == "--capacity" {
+                    capacity = Some(value);
+                } else {
+                    budget = Some(value);
+                }
+            },
+            "--depot" => match arguments.next().map(|v| v.parse::<usize>()) {
+                Some(Ok(v)) => depot = v,
+                _ => return Err(format!("{} expects a vertex index", option))
+            },
+            "--salesmen" => match arguments.next().map(|m| m.parse::<usize>()) {
+                Some(Ok(m)) => salesmen = Some(m),
+                _ => return Err(format!("{} expects a number", option))
+            },
+            "--objective" => match arguments.next().map(|o| o.as_str()) {
+                Some("sum") => objective = FleetObjective::MinSum,
+                Some("max") => objective = FleetObjective::MinMax,
+                _ => return Err(format!("{} expects sum or max", option))
+            },
+            "--cost" => match arguments.next().and_then(|name| Objective::from_name(name)) {
+                Some(objective) => cost = objective,
+                None => return Err(format!("{} expects {}", option, Objective::ALL.iter().map(|o| o.to_string()).collect::<Vec<String>>().join(", ")))
+            },
+            "--via" => match arguments.next().and_then(|name| Solver::from_name(name)) {
+                Some(solver) => via = Some(solver),
+                None => return Err(format!("{} expects an algorithm", option))
+            },
+            _ => return Err(format!("Unknown option {}", option))
+        }
+    }
+
+    let mode: RouteMode = if is_path { RouteMode::Path { start, end } } else { RouteMode::Cycle };
+
+    Ok(Options {
+        mode,
+        windows,
+        demands,
+        capacity,
+        depot,
+        salesmen,
+        objective,
+        prizes,
+        budget,
+        clusters,
+        via,
+        precedences,
+        cost
+    })
+}
+
+// Agenda da rota com janelas de tempo, uma linha por visita, e as cidades atendidas com atraso
+fn print_schedule(route: &[usize], schedule: &Schedule, windows: &[TimeWindow]) {
+    println!("Vertex | Window | Arrival | Wait | Start | Departure | Lateness");
+
+    for visit in schedule.get_visits() {
+        let window: &TimeWindow = &windows[visit.get_vertex()];
+        println!("{} | [{}, {}] | {} | {} | {} | {} | {}", visit.get_vertex(), window.get_earliest(), window.get_latest(), visit.get_arrival(), visit.get_wait(), visit.get_start(), visit.get_departure(), visit.get_lateness());
+    }
+
+    println!("Return to {} at {} (closes at {})", route[0], schedule.get_return_arrival(), windows[route[0]].get_latest());
+    println!("Travel cost: {} - Total wait: {} - Total lateness: {}", schedule.get_travel_cost(), schedule.get_total_wait(), schedule.get_total_lateness());
+
+    if schedule.is_feasible() {
+        println!("Feasible: every window is respected");
+    } else {
+        println!("Violations: {}", schedule.get_violations().iter().map(|v| v.to_string()).collect::<Vec<String>>().join(", "));
+    }
+}
+
+// Uma linha por veiculo com os clientes, a carga e o custo da rota
+fn print_vehicle_routes(depot: usize, routes: &[VehicleRoute]) {
+    for (i, route) in routes.iter().enumerate() {
+        let stops: Vec<String> = [depot].iter().chain(route.get_customers().iter()).chain([depot].iter()).map(|v| v.to_string()).collect();
+        println!("Vehicle {}: {} - Load: {} - Cost: {}", i + 1, stops.join(" -> "), route.get_load(), route.get_cost());
+    }
+
+    println!("Vehicles: {} - Total cost: {}", routes.len(), routes.iter().map(|r| r.get_cost()).sum::<u64>());
+}
+
+// Relatorio da estrutura do grafo: tamanho, simetria e conectividade
+fn inspect(t: &Graph<usize>) -> Result<(), &'static str> {
+    let components: Vec<Vec<usize>> = t.get_connected_components()?;
+    let strong_components: Vec<Vec<usize>> = t.get_strongly_connected_components()?;
+    let bridges: Vec<(usize, usize)> = t.get_bridges()?;
+    let articulation_points: Vec<usize> = t.get_articulation_points()?;
+
+    println!("Vertices: {}", t.num_vertices());
+    println!("Edges: {}", t.edges().count());
+    println!("Coordinate-based: {}", t.is_implicit());
+    println!("Symmetric: {}", t.is_symmetric());
+    println!("Connected components: {}", components.len());
+    println!("Strongly connected components: {}", strong_components.len());
+    println!("Bridges: {}", bridges.iter().map(|(u, v)| format!("{}-{}", u, v)).collect::<Vec<String>>().join(", "));
+    println!("Articulation points: {}", articulation_points.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(", "));
+
+    if components.len() > 1 {
+        for (i, component) in components.iter().enumerate() {
+            println!("Component {}: {}", i, component.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(" "));
+        }
+    }
+
+    Ok(())
+}
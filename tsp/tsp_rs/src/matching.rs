@@ -0,0 +1,674 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use crate::graph::Graph;
+
+const NONE: usize = usize::MAX;
+
+// Emparelhamento de peso maximo com cardinalidade maxima pelo algoritmo blossom de Edmonds com variaveis duais,
+// na versao O(n³) de Van Rantwijk: http://jorisvr.nl/article/maximum-matching
+// Os indices 0..n sao os vertices e n..2n as blossoms. Cada aresta k tem as pontas 2k e 2k + 1: endpoint[p] é
+// o vertice da ponta p e p ^ 1 é a ponta oposta. Os rotulos sao 0 (livre), 1 (S) e 2 (T); o bit 4 marca as
+// blossoms visitadas em `scan_blossom`.
+struct Blossom {
+    n: usize,
+    edges: Vec<(usize, usize, i64)>,
+    endpoint: Vec<usize>,
+    // Pontas opostas das arestas de cada vertice
+    neighbor_endpoints: Vec<Vec<usize>>,
+    // Ponta da aresta do emparelhamento que chega em cada vertice
+    mate: Vec<usize>,
+    label: Vec<u8>,
+    // Ponta pela qual o vertice ou blossom recebeu o rotulo
+    label_end: Vec<usize>,
+    // Blossom de mais alto nivel que contem cada vertice
+    in_blossom: Vec<usize>,
+    blossom_parent: Vec<usize>,
+    // Sub-blossoms em ordem ciclica, comecando pela que contem a base
+    blossom_children: Vec<Vec<usize>>,
+    blossom_base: Vec<usize>,
+    // blossom_endpoints[b][i] liga blossom_children[b][i] a blossom_children[b][i + 1]
+    blossom_endpoints: Vec<Vec<usize>>,
+    // Aresta de menor folga ate uma blossom S (para vertices livres e blossoms S)
+    best_edge: Vec<usize>,
+    blossom_best_edges: Vec<Option<Vec<usize>>>,
+    unused_blossoms: Vec<usize>,
+    dual: Vec<i64>,
+    allowed_edge: Vec<bool>,
+    queue: Vec<usize>
+}
+
+impl Blossom {
+    fn new(n: usize, edges: Vec<(usize, usize, i64)>) -> Self {
+        let max_weight: i64 = edges.iter().map(|&(_, _, w)| w).max().unwrap_or(0).max(0);
+        let endpoint: Vec<usize> = (0..2 * edges.len()).map(|p| if p % 2 == 0 { edges[p / 2].0 } else { edges[p / 2].1 }).collect();
+        let mut neighbor_endpoints: Vec<Vec<usize>> = vec![Vec::new(); n];
+
+        for (k, &(i, j, _)) in edges.iter().enumerate() {
+            neighbor_endpoints[i].push(2 * k + 1);
+            neighbor_endpoints[j].push(2 * k);
+        }
+
+        Blossom {
+            n,
+            endpoint,
+            neighbor_endpoints,
+            mate: vec![NONE; n],
+            label: vec![0; 2 * n],
+            label_end: vec![NONE; 2 * n],
+            in_blossom: (0..n).collect(),
+            blossom_parent: vec![NONE; 2 * n],
+            blossom_children: vec![Vec::new(); 2 * n],
+            blossom_base: (0..n).chain(std::iter::repeat_n(NONE, n)).collect(),
+            blossom_endpoints: vec![Vec::new(); 2 * n],
+            best_edge: vec![NONE; 2 * n],
+            blossom_best_edges: vec![None; 2 * n],
+            unused_blossoms: (n..2 * n).collect(),
+            dual: std::iter::repeat_n(max_weight, n).chain(std::iter::repeat_n(0, n)).collect(),
+            allowed_edge: vec![false; edges.len()],
+            queue: Vec::new(),
+            edges
+        }
+    }
+
+    fn slack(&self, k: usize) -> i64 {
+        let (i, j, w) = self.edges[k];
+
+        self.dual[i] + self.dual[j] - 2 * w
+    }
+
+    // Vertices contidos na blossom b
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        let mut leaves: Vec<usize> = Vec::new();
+        let mut stack: Vec<usize> = vec![b];
+
+        while let Some(t) = stack.pop() {
+            if t < self.n {
+                leaves.push(t);
+            } else {
+                stack.extend(self.blossom_children[t].iter().rev());
+            }
+        }
+
+        leaves
+    }
+
+    // Rotula o vertice w e sua blossom com t, vindo pela ponta p. Uma blossom T rotula como S o vertice
+    // emparelhado com a sua base.
+    fn assign_label(&mut self, w: usize, t: u8, p: usize) {
+        let b: usize = self.in_blossom[w];
+
+        self.label[w] = t;
+        self.label[b] = t;
+        self.label_end[w] = p;
+        self.label_end[b] = p;
+        self.best_edge[w] = NONE;
+        self.best_edge[b] = NONE;
+
+        if t == 1 {
+            let leaves: Vec<usize> = self.leaves(b);
+            self.queue.extend(leaves);
+        } else {
+            let base_mate: usize = self.mate[self.blossom_base[b]];
+            self.assign_label(self.endpoint[base_mate], 1, base_mate ^ 1);
+        }
+    }
+
+    // Sobe pelas arvores alternantes a partir de v e w. Retorna a base da nova blossom, ou NONE se as
+    // arvores sao diferentes e existe um caminho aumentante.
+    fn scan_blossom(&mut self, mut v: usize, mut w: usize) -> usize {
+        let mut path: Vec<usize> = Vec::new();
+        let mut base: usize = NONE;
+
+        while v != NONE || w != NONE {
+            let mut b: usize = self.in_blossom[v];
+
+            if self.label[b] & 4 != 0 {
+                base = self.blossom_base[b];
+                break;
+            }
+
+            path.push(b);
+            self.label[b] = 5;
+
+            if self.label_end[b] == NONE {
+                v = NONE;
+            } else {
+                v = self.endpoint[self.label_end[b]];
+                b = self.in_blossom[v];
+                v = self.endpoint[self.label_end[b]];
+            }
+
+            if w != NONE {
+                std::mem::swap(&mut v, &mut w);
+            }
+        }
+
+        for b in path {
+            self.label[b] = 1;
+        }
+
+        base
+    }
+
+    // Contrai o ciclo fechado pela aresta k numa blossom S com a base dada
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (mut v, mut w, _) = self.edges[k];
+        let bb: usize = self.in_blossom[base];
+        let mut bv: usize = self.in_blossom[v];
+        let mut bw: usize = self.in_blossom[w];
+        let b: usize = self.unused_blossoms.pop().unwrap();
+
+        self.blossom_base[b] = base;
+        self.blossom_parent[b] = NONE;
+        self.blossom_parent[bb] = b;
+
+        let mut path: Vec<usize> = Vec::new();
+        let mut endpoints: Vec<usize> = Vec::new();
+
+        while bv != bb {
+            self.blossom_parent[bv] = b;
+            path.push(bv);
+            endpoints.push(self.label_end[bv]);
+            v = self.endpoint[self.label_end[bv]];
+            bv = self.in_blossom[v];
+        }
+
+        path.push(bb);
+        path.reverse();
+        endpoints.reverse();
+        endpoints.push(2 * k);
+
+        while bw != bb {
+            self.blossom_parent[bw] = b;
+            path.push(bw);
+            endpoints.push(self.label_end[bw] ^ 1);
+            w = self.endpoint[self.label_end[bw]];
+            bw = self.in_blossom[w];
+        }
+
+        self.blossom_children[b] = path.clone();
+        self.blossom_endpoints[b] = endpoints;
+        self.label[b] = 1;
+        self.label_end[b] = self.label_end[bb];
+        self.dual[b] = 0;
+
+        for v in self.leaves(b) {
+            if self.label[self.in_blossom[v]] == 2 {
+                self.queue.push(v);
+            }
+
+            self.in_blossom[v] = b;
+        }
+
+        // Menor folga da nova blossom ate cada blossom S vizinha
+        let mut best_edge_to: Vec<usize> = vec![NONE; 2 * self.n];
+
+        for bv in path {
+            let lists: Vec<Vec<usize>> = match self.blossom_best_edges[bv].take() {
+                Some(list) => vec![list],
+                None => self.leaves(bv).into_iter().map(|v| self.neighbor_endpoints[v].iter().map(|p| p / 2).collect()).collect()
+            };
+
+            for k in lists.into_iter().flatten() {
+                let (i, j, _) = self.edges[k];
+                let j: usize = if self.in_blossom[j] == b { i } else { j };
+                let bj: usize = self.in_blossom[j];
+
+                if bj != b && self.label[bj] == 1 && (best_edge_to[bj] == NONE || self.slack(k) < self.slack(best_edge_to[bj])) {
+                    best_edge_to[bj] = k;
+                }
+            }
+
+            self.best_edge[bv] = NONE;
+        }
+
+        let best_edges: Vec<usize> = best_edge_to.into_iter().filter(|&k| k != NONE).collect();
+        self.best_edge[b] = best_edges.iter().copied().min_by_key(|&k| self.slack(k)).unwrap_or(NONE);
+        self.blossom_best_edges[b] = Some(best_edges);
+    }
+
+    // Desfaz a blossom b. No meio de uma fase, se b era T, reconstroi os rotulos do caminho alternante que
+    // atravessa a blossom; no fim da fase expande tambem as sub-blossoms com dual nulo.
+    fn expand_blossom(&mut self, b: usize, end_stage: bool) {
+        let children: Vec<usize> = self.blossom_children[b].clone();
+
+        for &s in children.iter() {
+            self.blossom_parent[s] = NONE;
+
+            if s < self.n {
+                self.in_blossom[s] = s;
+            } else if end_stage && self.dual[s] == 0 {
+                self.expand_blossom(s, end_stage);
+            } else {
+                for v in self.leaves(s) {
+                    self.in_blossom[v] = s;
+                }
+            }
+        }
+
+        if !end_stage && self.label[b] == 2 {
+            let endpoints: Vec<usize> = self.blossom_endpoints[b].clone();
+            let length: isize = children.len() as isize;
+            let at = |j: isize| -> usize { j.rem_euclid(length) as usize };
+
+            let entry_child: usize = self.in_blossom[self.endpoint[self.label_end[b] ^ 1]];
+            let mut j: isize = children.iter().position(|&c| c == entry_child).unwrap() as isize;
+            let (step, trick): (isize, usize) = if j & 1 == 1 {
+                j -= length;
+                (1, 0)
+            } else {
+                (-1, 1)
+            };
+
+            let mut p: usize = self.label_end[b];
+
+            while j != 0 {
+                self.label[self.endpoint[p ^ 1]] = 0;
+                self.label[self.endpoint[endpoints[at(j - trick as isize)] ^ trick ^ 1]] = 0;
+                self.assign_label(self.endpoint[p ^ 1], 2, p);
+                self.allowed_edge[endpoints[at(j - trick as isize)] / 2] = true;
+                j += step;
+                p = endpoints[at(j - trick as isize)] ^ trick;
+                self.allowed_edge[p / 2] = true;
+                j += step;
+            }
+
+            let bv: usize = children[at(j)];
+            self.label[self.endpoint[p ^ 1]] = 2;
+            self.label[bv] = 2;
+            self.label_end[self.endpoint[p ^ 1]] = p;
+            self.label_end[bv] = p;
+            self.best_edge[bv] = NONE;
+            j += step;
+
+            while children[at(j)] != entry_child {
+                let bv: usize = children[at(j)];
+
+                if self.label[bv] == 1 {
+                    j += step;
+                    continue;
+                }
+
+                if let Some(v) = self.leaves(bv).into_iter().find(|&v| self.label[v] != 0) {
+                    self.label[v] = 0;
+                    self.label[self.endpoint[self.mate[self.blossom_base[bv]]]] = 0;
+                    self.assign_label(v, 2, self.label_end[v]);
+                }
+
+                j += step;
+            }
+        }
+
+        self.label[b] = 0;
+        self.label_end[b] = NONE;
+        self.blossom_children[b].clear();
+        self.blossom_endpoints[b].clear();
+        self.blossom_base[b] = NONE;
+        self.blossom_best_edges[b] = None;
+        self.best_edge[b] = NONE;
+        self.unused_blossoms.push(b);
+    }
+
+    // Troca as arestas do emparelhamento dentro da blossom b para que o vertice v vire a base
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t: usize = v;
+
+        while self.blossom_parent[t] != b {
+            t = self.blossom_parent[t];
+        }
+
+        if t >= self.n {
+            self.augment_blossom(t, v);
+        }
+
+        let children: Vec<usize> = self.blossom_children[b].clone();
+        let endpoints: Vec<usize> = self.blossom_endpoints[b].clone();
+        let length: isize = children.len() as isize;
+        let at = |j: isize| -> usize { j.rem_euclid(length) as usize };
+
+        let i: usize = children.iter().position(|&c| c == t).unwrap();
+        let mut j: isize = i as isize;
+        let (step, trick): (isize, usize) = if i & 1 == 1 {
+            j -= length;
+            (1, 0)
+        } else {
+            (-1, 1)
+        };
+
+        while j != 0 {
+            j += step;
+            let t: usize = children[at(j)];
+            let p: usize = endpoints[at(j - trick as isize)] ^ trick;
+
+            if t >= self.n {
+                self.augment_blossom(t, self.endpoint[p]);
+            }
+
+            j += step;
+            let t: usize = children[at(j)];
+
+            if t >= self.n {
+                self.augment_blossom(t, self.endpoint[p ^ 1]);
+            }
+
+            self.mate[self.endpoint[p]] = p ^ 1;
+            self.mate[self.endpoint[p ^ 1]] = p;
+        }
+
+        self.blossom_children[b].rotate_left(i);
+        self.blossom_endpoints[b].rotate_left(i);
+        self.blossom_base[b] = self.blossom_base[self.blossom_children[b][0]];
+    }
+
+    // Aumenta o emparelhamento pelo caminho que passa pela aresta k, subindo pelas duas arvores ate as raizes
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k];
+
+        for (mut s, mut p) in [(v, 2 * k + 1), (w, 2 * k)] {
+            loop {
+                let bs: usize = self.in_blossom[s];
+
+                if bs >= self.n {
+                    self.augment_blossom(bs, s);
+                }
+
+                self.mate[s] = p;
+
+                if self.label_end[bs] == NONE {
+                    break;
+                }
+
+                let t: usize = self.endpoint[self.label_end[bs]];
+                let bt: usize = self.in_blossom[t];
+                s = self.endpoint[self.label_end[bt]];
+                let j: usize = self.endpoint[self.label_end[bt] ^ 1];
+
+                if bt >= self.n {
+                    self.augment_blossom(bt, j);
+                }
+
+                self.mate[j] = self.label_end[bt];
+                p = self.label_end[bt] ^ 1;
+            }
+        }
+    }
+
+    // Cada fase procura um caminho aumentante, ajustando os duais quando a busca trava. Retorna o par de
+    // cada vertice (NONE se ficou livre).
+    fn solve(mut self) -> Vec<usize> {
+        let n: usize = self.n;
+
+        for _ in 0..n {
+            self.label.fill(0);
+            self.best_edge.fill(NONE);
+            self.blossom_best_edges[n..].fill(None);
+            self.allowed_edge.fill(false);
+            self.queue.clear();
+
+            for v in 0..n {
+                if self.mate[v] == NONE && self.label[self.in_blossom[v]] == 0 {
+                    self.assign_label(v, 1, NONE);
+                }
+            }
+
+            let mut augmented: bool = false;
+
+            loop {
+                while !augmented {
+                    let v: usize = match self.queue.pop() {
+                        Some(v) => v,
+                        None => break
+                    };
+
+                    for idx in 0..self.neighbor_endpoints[v].len() {
+                        let p: usize = self.neighbor_endpoints[v][idx];
+                        let k: usize = p / 2;
+                        let w: usize = self.endpoint[p];
+
+                        if self.in_blossom[v] == self.in_blossom[w] {
+                            continue;
+                        }
+
+                        let mut k_slack: i64 = 0;
+
+                        if !self.allowed_edge[k] {
+                            k_slack = self.slack(k);
+
+                            if k_slack <= 0 {
+                                self.allowed_edge[k] = true;
+                            }
+                        }
+
+                        if self.allowed_edge[k] {
+                            if self.label[self.in_blossom[w]] == 0 {
+                                self.assign_label(w, 2, p ^ 1);
+                            } else if self.label[self.in_blossom[w]] == 1 {
+                                let base: usize = self.scan_blossom(v, w);
+
+                                if base != NONE {
+                                    self.add_blossom(base, k);
+                                } else {
+                                    self.augment_matching(k);
+                                    augmented = true;
+                                    break;
+                                }
+                            } else if self.label[w] == 0 {
+                                self.label[w] = 2;
+                                self.label_end[w] = p ^ 1;
+                            }
+                        } else if self.label[self.in_blossom[w]] == 1 {
+                            let b: usize = self.in_blossom[v];
+
+                            if self.best_edge[b] == NONE || k_slack < self.slack(self.best_edge[b]) {
+                                self.best_edge[b] = k;
+                            }
+                        } else if self.label[w] == 0 && (self.best_edge[w] == NONE || k_slack < self.slack(self.best_edge[w])) {
+                            self.best_edge[w] = k;
+                        }
+                    }
+                }
+
+                if augmented {
+                    break;
+                }
+
+                // Tipos de ajuste: 2 libera uma aresta S-livre, 3 uma aresta S-S e 4 expande uma blossom T.
+                // Como a cardinalidade é maxima, sem nenhum deles a fase termina (tipo 1).
+                let mut delta: Option<(i64, u8, usize)> = None;
+
+                for v in 0..n {
+                    if self.label[self.in_blossom[v]] == 0 && self.best_edge[v] != NONE {
+                        let d: i64 = self.slack(self.best_edge[v]);
+
+                        if delta.is_none_or(|(actual, _, _)| d < actual) {
+                            delta = Some((d, 2, self.best_edge[v]));
+                        }
+                    }
+                }
+
+                for b in 0..2 * n {
+                    if self.blossom_parent[b] == NONE && self.label[b] == 1 && self.best_edge[b] != NONE {
+                        let d: i64 = self.slack(self.best_edge[b]) / 2;
+
+                        if delta.is_none_or(|(actual, _, _)| d < actual) {
+                            delta = Some((d, 3, self.best_edge[b]));
+                        }
+                    }
+                }
+
+                for b in n..2 * n {
+                    if self.blossom_base[b] != NONE && self.blossom_parent[b] == NONE && self.label[b] == 2
+                        && delta.is_none_or(|(actual, _, _)| self.dual[b] < actual) {
+                        delta = Some((self.dual[b], 4, b));
+                    }
+                }
+
+                let (delta, delta_type, delta_target) = delta.unwrap_or((self.dual[..n].iter().copied().min().unwrap_or(0).max(0), 1, NONE));
+
+                for v in 0..n {
+                    match self.label[self.in_blossom[v]] {
+                        1 => self.dual[v] -= delta,
+                        2 => self.dual[v] += delta,
+                        _ => {}
+                    }
+                }
+
+                for b in n..2 * n {
+                    if self.blossom_base[b] != NONE && self.blossom_parent[b] == NONE {
+                        match self.label[b] {
+                            1 => self.dual[b] += delta,
+                            2 => self.dual[b] -= delta,
+                            _ => {}
+                        }
+                    }
+                }
+
+                match delta_type {
+                    1 => break,
+                    2 => {
+                        self.allowed_edge[delta_target] = true;
+                        let (i, j, _) = self.edges[delta_target];
+                        self.queue.push(if self.label[self.in_blossom[i]] == 0 { j } else { i });
+                    },
+                    3 => {
+                        self.allowed_edge[delta_target] = true;
+                        self.queue.push(self.edges[delta_target].0);
+                    },
+                    _ => self.expand_blossom(delta_target, false)
+                }
+            }
+
+            if !augmented {
+                break;
+            }
+
+            for b in n..2 * n {
+                if self.blossom_parent[b] == NONE && self.blossom_base[b] != NONE && self.label[b] == 1 && self.dual[b] == 0 {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+
+        self.mate.iter().map(|&p| if p == NONE { NONE } else { self.endpoint[p] }).collect()
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Emparelhamento perfeito de peso minimo, tratando o grafo como completo e usando o peso de i para j (i < j).
+    // Os pesos viram `maior peso - peso` e o blossom procura o emparelhamento de peso maximo entre os de
+    // cardinalidade maxima, que num grafo completo com numero par de vertices é perfeito. Custa O(n³).
+    // Retorna os pares em vez de um grafo, pois arestas de peso 0 (cidades repetidas) sumiriam da matriz.
+    pub fn perfect_matching(&self) -> Result<Vec<(usize, usize)>, &'static str> {
+        if !self.is_squared() {
+            return Err("Graph is not squared!");
+        }
+
+        if !self.num_vertices().is_multiple_of(2) {
+            return Err("Graph has an odd number of vertices!");
+        }
+
+        let n: usize = self.num_vertices();
+        let max_weight: u64 = (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j))).map(|(i, j)| self.weight(i, j)).max().unwrap_or(0);
+
+        if max_weight > i64::MAX as u64 / 4 {
+            return Err("Edge weights are too large!");
+        }
+
+        let edges: Vec<(usize, usize, i64)> = (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .map(|(i, j)| (i, j, (max_weight - self.weight(i, j)) as i64))
+            .collect();
+
+        let mate: Vec<usize> = Blossom::new(n, edges).solve();
+
+        Ok((0..n).filter(|&i| i < mate[i]).map(|i| (i, mate[i])).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::next_random;
+
+    // Emparelhamento perfeito minimo por programacao dinamica sobre subconjuntos, para conferir o blossom
+    fn brute_force(matrix: &[Vec<u64>]) -> u64 {
+        let n: usize = matrix.len();
+        let mut best: Vec<u64> = vec![u64::MAX; 1 << n];
+        best[0] = 0;
+
+        for mask in 0..1usize << n {
+            if best[mask] == u64::MAX || mask == (1 << n) - 1 {
+                continue;
+            }
+
+            let i: usize = (!mask).trailing_zeros() as usize;
+
+            for (j, &weight) in matrix[i].iter().enumerate().skip(i + 1) {
+                if mask & (1 << j) == 0 {
+                    let next: usize = mask | (1 << i) | (1 << j);
+                    best[next] = best[next].min(best[mask] + weight);
+                }
+            }
+        }
+
+        best[(1 << n) - 1]
+    }
+
+    fn random_matrix(n: usize, seed: &mut u64, max_weight: u64) -> Vec<Vec<u64>> {
+        let mut matrix: Vec<Vec<u64>> = vec![vec![0; n]; n];
+
+        for (i, j) in (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j))) {
+            matrix[i][j] = next_random(seed) % max_weight;
+            matrix[j][i] = matrix[i][j];
+        }
+
+        matrix
+    }
+
+    fn matching_cost(matrix: &[Vec<u64>], matching: &[(usize, usize)]) -> u64 {
+        let mut covered: Vec<bool> = vec![false; matrix.len()];
+
+        for &(i, j) in matching {
+            assert!(!covered[i] && !covered[j]);
+            covered[i] = true;
+            covered[j] = true;
+        }
+
+        assert!(covered.iter().all(|&c| c));
+
+        matching.iter().map(|&(i, j)| matrix[i][j]).sum()
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut seed: u64 = 7;
+
+        for n in [2, 4, 6, 8, 10, 12] {
+            for max_weight in [3, 100] {
+                for _ in 0..40 {
+                    let matrix: Vec<Vec<u64>> = random_matrix(n, &mut seed, max_weight);
+                    let t: Graph<usize> = Graph::from((0..n).collect(), matrix.clone());
+
+                    assert_eq!(matching_cost(&matrix, &t.perfect_matching().unwrap()), brute_force(&matrix));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn beats_greedy_choice() {
+        // O guloso liga 0-1 (peso 1) e fica com 2-3 (peso 100); o otimo é 0-2 e 1-3
+        let matrix: Vec<Vec<u64>> = vec![vec![0, 1, 2, 100], vec![1, 0, 100, 2], vec![2, 100, 0, 100], vec![100, 2, 100, 0]];
+        let t: Graph<usize> = Graph::from((0..4).collect(), matrix.clone());
+
+        assert_eq!(matching_cost(&matrix, &t.perfect_matching().unwrap()), 4);
+    }
+
+    #[test]
+    fn small_and_invalid_graphs() {
+        assert_eq!(Graph::<usize>::new().perfect_matching(), Ok(Vec::new()));
+        assert!(Graph::from(vec![0], vec![vec![0]]).perfect_matching().is_err());
+        assert_eq!(Graph::from(vec![0, 1], vec![vec![0, 0], vec![0, 0]]).perfect_matching(), Ok(vec![(0, 1)]));
+        assert!(Graph::from(vec![0, 1], vec![vec![0, 1], vec![1]]).perfect_matching().is_err());
+    }
+}
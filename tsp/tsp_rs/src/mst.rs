@@ -0,0 +1,567 @@
+use std::collections::BinaryHeap;
+use std::cmp::Reverse;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::thread;
+use crate::edge::Edge;
+use crate::graph::Graph;
+use crate::multigraph::Multigraph;
+use crate::union_find::UnionFind;
+
+// Resultado dos algoritmos de arvore geradora minima. Em grafos desconexos é uma floresta,
+// com uma arvore por componente (ver `num_components`).
+pub struct SpanningTree {
+    edges: Vec<Edge<usize>>,
+    total_weight: u64,
+    num_vertices: usize,
+    num_components: usize
+}
+
+impl SpanningTree {
+    fn from_edges(num_vertices: usize, edges: Vec<Edge<usize>>) -> Self {
+        let total_weight: u64 = edges.iter().map(|e| e.get_weight()).sum();
+
+        SpanningTree {
+            num_components: num_vertices - edges.len(),
+            edges,
+            total_weight,
+            num_vertices
+        }
+    }
+
+    // Arestas da arvore por indice de vertice, na ordem em que foram escolhidas
+    pub fn get_edges(&self) -> &Vec<Edge<usize>> {
+        &self.edges
+    }
+
+    pub fn get_total_weight(&self) -> u64 {
+        self.total_weight
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.num_vertices
+    }
+
+    pub fn num_components(&self) -> usize {
+        self.num_components
+    }
+
+    // Verdadeiro quando o grafo era conexo e o resultado é uma unica arvore
+    pub fn is_spanning_tree(&self) -> bool {
+        self.num_components <= 1
+    }
+
+    // Grafo com os vertices de `graph` e as arestas da arvore nas duas direcoes
+    pub fn to_graph<V: Eq + Hash + Display + Clone>(&self, graph: &Graph<V>) -> Result<Graph<V>, &'static str> {
+        let n: usize = self.num_vertices;
+        let mut tree: Graph<V> = Graph::from(graph.vertices().map(|(_, v)| v.clone()).collect(), vec![vec![0; n]; n]);
+
+        for edge in self.edges.iter() {
+            tree.insert_edge(*edge.get_src(), *edge.get_dst(), edge.get_weight(), false)?;
+        }
+
+        Ok(tree)
+    }
+
+    // Multigrafo com os vertices de `graph` e as arestas da arvore, mantendo arestas de peso 0
+    pub fn to_multigraph<V: Eq + Hash + Display + Clone>(&self, graph: &Graph<V>) -> Result<Multigraph<V>, &'static str> {
+        let mut multigraph: Multigraph<V> = Multigraph::new();
+
+        for (_, vertex) in graph.vertices() {
+            multigraph.insert_vertex(vertex.clone());
+        }
+
+        for edge in self.edges.iter() {
+            multigraph.insert_edge(*edge.get_src(), *edge.get_dst(), edge.get_weight())?;
+        }
+
+        Ok(multigraph)
+    }
+}
+
+// Chu-Liu/Edmonds recursivo sobre uma lista de arcos (src, dst, peso). Retorna os indices dos arcos escolhidos
+// (um chegando em cada vertice que nao é a raiz), ou None quando algum vertice nao recebe arco.
+fn edmonds(n: usize, root: usize, arcs: &[(usize, usize, u64)]) -> Option<Vec<usize>> {
+    // Arco mais barato chegando em cada vertice
+    let mut best_in: Vec<Option<usize>> = vec![None; n];
+
+    for (i, &(src, dst, weight)) in arcs.iter().enumerate() {
+        if src != dst && dst != root && best_in[dst].is_none_or(|b| weight < arcs[b].2) {
+            best_in[dst] = Some(i);
+        }
+    }
+
+    if (0..n).any(|v| v != root && best_in[v].is_none()) {
+        return None;
+    }
+
+    // Procura ciclos seguindo os arcos escolhidos de tras para frente
+    let mut cycle_of: Vec<Option<usize>> = vec![None; n];
+    let mut mark: Vec<Option<usize>> = vec![None; n];
+    let mut num_cycles: usize = 0;
+
+    for start in 0..n {
+        let mut v: usize = start;
+
+        while v != root && mark[v].is_none() {
+            mark[v] = Some(start);
+            v = arcs[best_in[v].unwrap()].0;
+        }
+
+        // Voltou a um vertice marcado nesta mesma caminhada: v esta num ciclo novo
+        if v != root && mark[v] == Some(start) && cycle_of[v].is_none() {
+            let mut u: usize = v;
+
+            loop {
+                cycle_of[u] = Some(num_cycles);
+                u = arcs[best_in[u].unwrap()].0;
+
+                if u == v {
+                    break;
+                }
+            }
+
+            num_cycles += 1;
+        }
+    }
+
+    if num_cycles == 0 {
+        return Some((0..n).filter_map(|v| best_in[v]).collect());
+    }
+
+    // Contrai cada ciclo num vertice so; os demais vertices ganham indices novos
+    let mut component: Vec<usize> = vec![0; n];
+    let mut num_components: usize = num_cycles;
+
+    for v in 0..n {
+        component[v] = match cycle_of[v] {
+            Some(c) => c,
+            None => {
+                num_components += 1;
+                num_components - 1
+            }
+        };
+    }
+
+    // Arcos entrando num ciclo pagam a diferenca para o arco do ciclo que eles substituem
+    let mut contracted_arcs: Vec<(usize, usize, u64)> = Vec::new();
+    let mut original: Vec<usize> = Vec::new();
+
+    for (i, &(src, dst, weight)) in arcs.iter().enumerate() {
+        if component[src] == component[dst] || dst == root {
+            continue;
+        }
+
+        let reduced: u64 = match cycle_of[dst] {
+            Some(_) => weight - arcs[best_in[dst].unwrap()].2,
+            None => weight
+        };
+
+        contracted_arcs.push((component[src], component[dst], reduced));
+        original.push(i);
+    }
+
+    let chosen: Vec<usize> = edmonds(num_components, component[root], &contracted_arcs)?;
+
+    // Expande: cada ciclo fica com todos os seus arcos menos o do vertice por onde o arco escolhido entra
+    let mut in_arc: Vec<Option<usize>> = (0..n).map(|v| if cycle_of[v].is_some() { best_in[v] } else { None }).collect();
+
+    for c in chosen {
+        let i: usize = original[c];
+        in_arc[arcs[i].1] = Some(i);
+    }
+
+    Some(in_arc.into_iter().flatten().collect())
+}
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Algoritmo de Kruskal: o conjunto A é  uma floresta cujos vértices são todos os vértices do grafo e a aresta segura
+    //   adicionada é sempre uma aresta de peso mínimo no grafo que conecta duas componentes distintas.
+    // As componentes ficam num union-find, entao cada aresta custa quase O(1) depois da ordenacao.
+    pub fn get_mst_kruskal(&self) -> SpanningTree {
+        let mut components: UnionFind = UnionFind::new(self.num_vertices());
+        let mut tree_edges: Vec<Edge<usize>> = Vec::with_capacity(self.num_vertices().saturating_sub(1));
+
+        // Ordenando as arestas
+        let mut edges: Vec<Edge<usize>> = self.undirected_edges().collect();
+        edges.sort_unstable_by_key(|e| e.get_weight());
+
+        for edge in edges.into_iter() {
+            // Caso os dois não estejam no mesmo conjunto, insere-se essa aresta e junta os conjuntos
+            if components.union(*edge.get_src(), *edge.get_dst()) {
+                tree_edges.push(edge);
+
+                if components.num_sets() == 1 {
+                    break;
+                }
+            }
+        }
+
+        SpanningTree::from_edges(self.num_vertices(), tree_edges)
+    }
+
+    // Algoritmo de Prim: inicia adicionando ao conjunto A os vertices ligados pela aresta de menor custo
+    //      e após vai adicionando os vertices que tiverem menor custo e estejam sejam adjacentes aos já existentes
+    // https://pt.wikipedia.org/wiki/Algoritmo_de_Prim
+    // Quando a heap esvazia antes de alcancar todos os vertices, uma nova arvore comeca no proximo vertice livre.
+    pub fn get_mst_prim(&self) -> SpanningTree {
+        let n: usize = self.num_vertices();
+        let mut in_tree: Vec<bool> = vec![false; n];
+        let mut heap: BinaryHeap<Reverse<(u64, (usize, usize))>> = BinaryHeap::new();
+        let mut tree_edges: Vec<Edge<usize>> = Vec::with_capacity(n.saturating_sub(1));
+
+        for root in 0..n {
+            if in_tree[root] {
+                continue;
+            }
+
+            in_tree[root] = true;
+
+            for (adj_vertex, weight) in self.neighbors(root).unwrap() {
+                heap.push(Reverse((weight, (root, adj_vertex))));
+            }
+
+            // Remove da heap
+            while let Some(Reverse((weight, (src, dst)))) = heap.pop() {
+                if in_tree[dst] {
+                    continue;
+                }
+
+                // Insere um vertice que nao havia sido explorado ainda
+                in_tree[dst] = true;
+                tree_edges.push(Edge::new(src, dst, weight));
+
+                for (adj_vertex, weight) in self.neighbors(dst).unwrap() {
+                    if in_tree[adj_vertex] {
+                        continue;
+                    }
+
+                    // Popula a heap denovo
+                    heap.push(Reverse((weight, (dst, adj_vertex))));
+                }
+            }
+        }
+
+        SpanningTree::from_edges(n, tree_edges)
+    }
+
+    // Prim com vetores no lugar da heap, O(n²): melhor opcao para grafos completos (matrizes cheias ou
+    // coordenadas), onde a heap teria O(n²) entradas.
+    pub fn get_mst_prim_dense(&self) -> SpanningTree {
+        let n: usize = self.num_vertices();
+        let mut in_tree: Vec<bool> = vec![false; n];
+        // Menor custo conhecido para ligar cada vertice a arvore e por qual vertice
+        let mut min_cost: Vec<Option<(u64, usize)>> = vec![None; n];
+        let mut tree_edges: Vec<Edge<usize>> = Vec::with_capacity(n.saturating_sub(1));
+
+        for _ in 0..n {
+            // Vertice fora da arvore mais barato de ligar; sem nenhum ligavel, comeca uma nova arvore
+            let mut next: Option<usize> = None;
+
+            for v in 0..n {
+                if in_tree[v] {
+                    continue;
+                }
+
+                next = match (next, min_cost[v]) {
+                    (None, _) => Some(v),
+                    (Some(u), Some((cost, _))) if min_cost[u].is_none_or(|(best, _)| cost < best) => Some(v),
+                    (actual, _) => actual
+                };
+            }
+
+            let u: usize = match next {
+                Some(u) => u,
+                None => break
+            };
+
+            in_tree[u] = true;
+
+            if let Some((cost, parent)) = min_cost[u] {
+                tree_edges.push(Edge::new(parent, u, cost));
+            }
+
+            for (v, weight) in self.neighbors(u).unwrap() {
+                if !in_tree[v] && min_cost[v].is_none_or(|(cost, _)| weight < cost) {
+                    min_cost[v] = Some((weight, u));
+                }
+            }
+        }
+
+        SpanningTree::from_edges(n, tree_edges)
+    }
+
+    // Algoritmo de Boruvka: https://en.wikipedia.org/wiki/Bor%C5%AFvka%27s_algorithm
+    // A cada rodada cada componente escolhe a sua aresta mais barata para fora e as componentes sao
+    // unidas pelo union-find. A busca das arestas é dividida entre as threads disponiveis, o que
+    // compensa em grafos grandes baseados em coordenadas. Em grafos desconexos gera uma floresta.
+    pub fn get_mst_boruvka(&self) -> SpanningTree
+    where
+        V: Sync
+    {
+        let n: usize = self.num_vertices();
+        let num_threads: usize = thread::available_parallelism().map_or(1, |p| p.get()).min(n.max(1));
+        let chunk_size: usize = n.div_ceil(num_threads).max(1);
+        let mut components: UnionFind = UnionFind::new(n);
+        let mut tree_edges: Vec<Edge<usize>> = Vec::with_capacity(n.saturating_sub(1));
+
+        loop {
+            // Copia dos representantes para as threads consultarem sem precisar de lock
+            let component_of: Vec<usize> = (0..n).map(|i| components.find(i)).collect();
+
+            // Cada thread calcula a aresta mais barata que sai de cada um dos seus vertices para outra componente.
+            // Empates sao desfeitos pelos indices para que todas as componentes concordem e nao formem ciclo.
+            let cheapest_per_vertex: Vec<Option<(u64, usize, usize)>> = thread::scope(|scope| {
+                let handles: Vec<_> = (0..n).step_by(chunk_size).map(|start| {
+                    let component_of: &Vec<usize> = &component_of;
+
+                    scope.spawn(move || {
+                        (start..(start + chunk_size).min(n)).map(|u| {
+                            self.neighbors(u).unwrap()
+                                .filter(|&(v, _)| component_of[u] != component_of[v])
+                                .map(|(v, w)| (w, u.min(v), u.max(v)))
+                                .min()
+                        }).collect::<Vec<Option<(u64, usize, usize)>>>()
+                    })
+                }).collect();
+
+                handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+            });
+
+            // Reduz para a aresta mais barata de cada componente
+            let mut cheapest: Vec<Option<(u64, usize, usize)>> = vec![None; n];
+
+            for (u, best) in cheapest_per_vertex.into_iter().enumerate() {
+                if let Some(edge) = best {
+                    let component: usize = component_of[u];
+
+                    if cheapest[component].is_none_or(|actual| edge < actual) {
+                        cheapest[component] = Some(edge);
+                    }
+                }
+            }
+
+            let mut merged: bool = false;
+
+            for (weight, u, v) in cheapest.into_iter().flatten() {
+                if components.union(u, v) {
+                    tree_edges.push(Edge::new(u, v, weight));
+                    merged = true;
+                }
+            }
+
+            // Nenhuma componente tem aresta para fora: arvore (ou floresta) completa
+            if !merged {
+                break;
+            }
+        }
+
+        SpanningTree::from_edges(n, tree_edges)
+    }
+
+    // Arborescencia geradora minima (Chu-Liu/Edmonds): https://en.wikipedia.org/wiki/Edmonds%27_algorithm
+    // Versao para grafos direcionados: cada vertice recebe exatamente um arco e todos sao alcancaveis a
+    // partir de `root_idx`. As arestas do resultado vao do pai para o filho. O(n·m).
+    pub fn get_min_arborescence(&self, root_idx: usize) -> Result<SpanningTree, &'static str> {
+        if self.num_vertices() <= root_idx {
+            return Err("Index out of range!");
+        }
+
+        let arcs: Vec<(usize, usize, u64)> = self.edges().map(|e| (*e.get_src(), *e.get_dst(), e.get_weight())).collect();
+
+        match edmonds(self.num_vertices(), root_idx, &arcs) {
+            Some(chosen) => Ok(SpanningTree::from_edges(self.num_vertices(), chosen.into_iter().map(|i| Edge::new(arcs[i].0, arcs[i].1, arcs[i].2)).collect())),
+            None => Err("Root does not reach every vertex!")
+        }
+    }
+
+    // Limitante inferior da 1-arborescencia para o TSP assimetrico: tirando da rota o arco que volta para a
+    // raiz sobra um caminho hamiltoniano, que é uma arborescencia. Soma a arborescencia minima com o arco mais
+    // barato que chega na raiz. Como no `get_route_cost`, a matriz é tratada como completa.
+    pub fn get_one_arborescence_lower_bound(&self, root_idx: usize) -> Result<u64, &'static str> {
+        if !self.is_squared() {
+            return Err("Graph is not squared!");
+        }
+
+        let n: usize = self.num_vertices();
+
+        if n <= root_idx {
+            return Err("Index out of range!");
+        }
+
+        if n < 2 {
+            return Err("Graph needs at least two vertices!");
+        }
+
+        let arcs: Vec<(usize, usize, u64)> = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| i != j).map(move |j| (i, j)))
+            .map(|(i, j)| (i, j, self.get_edge_weight(i, j).unwrap()))
+            .collect();
+
+        let arborescence: u64 = edmonds(n, root_idx, &arcs).unwrap().into_iter().map(|i| arcs[i].2).sum();
+        let cheapest_in: u64 = (0..n).filter(|&i| i != root_idx).map(|i| self.get_edge_weight(i, root_idx).unwrap()).min().unwrap();
+
+        Ok(arborescence + cheapest_in)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::next_random;
+
+    // Matriz simetrica com cerca de um terco dos pares sem aresta (peso 0)
+    fn random_symmetric_graph(n: usize, seed: &mut u64) -> Graph<usize> {
+        let mut matrix: Vec<Vec<u64>> = vec![vec![0; n]; n];
+
+        for (i, j) in (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j))) {
+            let weight: u64 = next_random(seed) % 30;
+            matrix[i][j] = if weight < 10 { 0 } else { weight };
+            matrix[j][i] = matrix[i][j];
+        }
+
+        Graph::from((0..n).collect(), matrix)
+    }
+
+    // Grafo do exemplo: quadrado 0-1-2-3 com a diagonal 0-2 e o vertice 4 pendurado em 3
+    fn example_graph() -> Graph<usize> {
+        let mut matrix: Vec<Vec<u64>> = vec![vec![0; 5]; 5];
+
+        for (u, v, w) in [(0, 1, 4), (1, 2, 2), (2, 3, 5), (3, 0, 1), (0, 2, 3), (3, 4, 7)] {
+            matrix[u][v] = w;
+            matrix[v][u] = w;
+        }
+
+        Graph::from((0..5).collect(), matrix)
+    }
+
+    #[test]
+    fn boruvka_known_answer() {
+        let tree: SpanningTree = example_graph().get_mst_boruvka();
+
+        assert_eq!(tree.get_total_weight(), 1 + 2 + 3 + 7);
+        assert_eq!(tree.get_edges().len(), 4);
+        assert!(tree.is_spanning_tree());
+    }
+
+    #[test]
+    fn boruvka_small_graphs() {
+        let single: SpanningTree = Graph::from(vec![0], vec![vec![0]]).get_mst_boruvka();
+        let pair: SpanningTree = Graph::from(vec![0, 1], vec![vec![0, 3], vec![3, 0]]).get_mst_boruvka();
+        // Peso 0 fora da diagonal é ausencia de aresta: sobra uma floresta com dois vertices soltos
+        let disconnected: SpanningTree = Graph::from(vec![0, 1], vec![vec![0, 0], vec![0, 0]]).get_mst_boruvka();
+
+        assert_eq!((single.get_total_weight(), single.num_components()), (0, 1));
+        assert_eq!((pair.get_total_weight(), pair.num_components()), (3, 1));
+        assert_eq!((disconnected.get_total_weight(), disconnected.num_components()), (0, 2));
+    }
+
+    #[test]
+    fn kruskal_and_prim_known_answer() {
+        let t: Graph<usize> = example_graph();
+
+        for tree in [t.get_mst_kruskal(), t.get_mst_prim(), t.get_mst_prim_dense()] {
+            assert_eq!(tree.get_total_weight(), 13);
+            assert_eq!(tree.num_components(), 1);
+        }
+
+        let graph: Graph<usize> = t.get_mst_kruskal().to_graph(&t).unwrap();
+        assert_eq!(graph.get_edge_weight(4, 3), Ok(7));
+        assert_eq!(graph.get_edge_weight(3, 4), Ok(7));
+        assert_eq!(graph.get_edge_weight(0, 1), Ok(0));
+    }
+
+    #[test]
+    fn all_algorithms_agree() {
+        let mut seed: u64 = 5;
+
+        for n in [1, 2, 3, 6, 10] {
+            for _ in 0..50 {
+                let t: Graph<usize> = random_symmetric_graph(n, &mut seed);
+                let kruskal: SpanningTree = t.get_mst_kruskal();
+
+                for tree in [t.get_mst_prim(), t.get_mst_prim_dense(), t.get_mst_boruvka()] {
+                    assert_eq!(tree.get_total_weight(), kruskal.get_total_weight());
+                    assert_eq!(tree.num_components(), kruskal.num_components());
+                }
+            }
+        }
+    }
+
+    // Menor arborescencia testando todas as escolhas de pai para os vertices fora da raiz
+    fn brute_force_arborescence(t: &Graph<usize>, root: usize) -> Option<u64> {
+        let n: usize = t.num_vertices();
+        let others: Vec<usize> = (0..n).filter(|&v| v != root).collect();
+        let mut best: Option<u64> = None;
+
+        for choice in 0..n.pow(others.len() as u32) {
+            let mut parent: Vec<usize> = vec![root; n];
+            let mut code: usize = choice;
+
+            for &v in others.iter() {
+                parent[v] = code % n;
+                code /= n;
+            }
+
+            // Todo vertice precisa de um arco do pai e chegar na raiz subindo
+            let valid: bool = others.iter().all(|&v| parent[v] != v && t.has_edge(parent[v], v))
+                && others.iter().all(|&v| {
+                    let mut actual: usize = v;
+                    (0..n).any(|_| {
+                        actual = parent[actual];
+                        actual == root
+                    })
+                });
+
+            if valid {
+                let cost: u64 = others.iter().map(|&v| t.weight(parent[v], v)).sum();
+                best = Some(best.map_or(cost, |b| b.min(cost)));
+            }
+        }
+
+        best
+    }
+
+    #[test]
+    fn arborescence_known_answer() {
+        // O arco barato 2 -> 1 forma ciclo com 1 -> 2; a solucao entra no ciclo por 0 -> 1
+        let t: Graph<usize> = Graph::from((0..3).collect(), vec![vec![0, 5, 6], vec![0, 0, 1], vec![0, 1, 0]]);
+        let tree: SpanningTree = t.get_min_arborescence(0).unwrap();
+
+        assert_eq!(tree.get_total_weight(), 6);
+        assert_eq!(tree.get_edges().len(), 2);
+        assert!(t.get_min_arborescence(1).is_err());
+        assert_eq!(t.get_one_arborescence_lower_bound(0), Ok(6));
+    }
+
+    #[test]
+    fn arborescence_matches_brute_force() {
+        let mut seed: u64 = 13;
+
+        for n in [1, 2, 3, 5] {
+            for _ in 0..60 {
+                let matrix: Vec<Vec<u64>> = (0..n).map(|i| (0..n).map(|j| if i == j { 0 } else { next_random(&mut seed) % 8 }).collect()).collect();
+                let t: Graph<usize> = Graph::from((0..n).collect(), matrix);
+
+                assert_eq!(t.get_min_arborescence(0).ok().map(|tree| tree.get_total_weight()), brute_force_arborescence(&t, 0));
+            }
+        }
+    }
+
+    #[test]
+    fn boruvka_builds_forests() {
+        let mut seed: u64 = 3;
+
+        for _ in 0..100 {
+            let t: Graph<usize> = random_symmetric_graph(9, &mut seed);
+            let tree: SpanningTree = t.get_mst_boruvka();
+            let mut components: UnionFind = UnionFind::new(9);
+
+            for edge in tree.get_edges() {
+                assert!(components.union(*edge.get_src(), *edge.get_dst()));
+            }
+
+            assert_eq!(tree.num_components(), t.get_connected_components().unwrap().len());
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::time::Instant;
+use crate::graph::Graph;
+use crate::solver::Solver;
+use crate::vrp::FleetObjective;
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Divide uma rota gigante (todas as cidades menos o deposito) em ate `salesmen` trechos consecutivos, um por
+    // caixeiro, escolhendo os cortes que otimizam o objetivo por programacao dinamica. O(m·n²).
+    pub fn mtsp_split(&self, depot_idx: usize, giant_tour: &[usize], salesmen: usize, objective: FleetObjective) -> Result<Vec<Vec<usize>>, &'static str> {
+        self.check_complete()?;
+
+        if self.num_vertices() <= depot_idx {
+            return Err("Index out of range!");
+        }
+
+        self.check_giant_tour(depot_idx, giant_tour)?;
+
+        if salesmen == 0 {
+            return Err("There must be at least one salesman!");
+        }
+
+        let k: usize = giant_tour.len();
+        // best[r][j]: (soma, maximo) para cobrir as j primeiras cidades com r trechos nao vazios
+        let mut best: Vec<Vec<Option<(u64, u64)>>> = vec![vec![None; k + 1]; salesmen + 1];
+        let mut cut: Vec<Vec<usize>> = vec![vec![0; k + 1]; salesmen + 1];
+        best[0][0] = Some((0, 0));
+
+        let key = |(sum, max): (u64, u64)| -> (u64, u64) { objective.key(sum, max) };
+
+        for r in 1..=salesmen {
+            for i in 0..k {
+                let Some((base_sum, base_max)) = best[r - 1][i] else { continue };
+                let mut inner: u64 = 0;
+
+                for j in i + 1..=k {
+                    if j > i + 1 {
+                        inner += self.weight(giant_tour[j - 2], giant_tour[j - 1]);
+                    }
+
+                    let cost: u64 = self.weight(depot_idx, giant_tour[i]) + inner + self.weight(giant_tour[j - 1], depot_idx);
+                    let candidate: (u64, u64) = (base_sum + cost, base_max.max(cost));
+
+                    if best[r][j].is_none_or(|actual| key(candidate) < key(actual)) {
+                        best[r][j] = Some(candidate);
+                        cut[r][j] = i;
+                    }
+                }
+            }
+        }
+
+        // Usa o numero de trechos que da o melhor resultado; os caixeiros que sobram ficam no deposito
+        let used: usize = (0..=salesmen).filter(|&r| best[r][k].is_some()).min_by_key(|&r| key(best[r][k].unwrap())).unwrap();
+        let mut routes: Vec<Vec<usize>> = vec![Vec::new(); salesmen];
+        let mut j: usize = k;
+
+        for r in (1..=used).rev() {
+            routes[r - 1] = giant_tour[cut[r][j]..j].to_vec();
+            j = cut[r][j];
+        }
+
+        Ok(routes)
+    }
+
+    // Varios caixeiros saindo e voltando ao deposito que juntos visitam todas as cidades. O objetivo pode ser a
+    // distancia total (min-sum) ou a rota mais longa (min-max). Divide a rota do 3-opt entre os caixeiros e
+    // melhora com a busca local das rotas de veiculos. Retorna uma rota por caixeiro, sem o deposito; caixeiros
+    // sem cidades ficam com a rota vazia.
+    pub fn mtsp_approx(&self, depot_idx: usize, salesmen: usize, objective: FleetObjective, log: bool) -> Result<Vec<Vec<usize>>, &'static str> {
+        self.check_complete()?;
+
+        if self.num_vertices() <= depot_idx {
+            return Err("Index out of range!");
+        }
+
+        if salesmen == 0 {
+            return Err("There must be at least one salesman!");
+        }
+
+        let start_time = Instant::now();
+
+        let (mut giant_tour, _) = self.solve(Solver::ThreeOpt, false)?;
+        let depot_position: usize = giant_tour.iter().position(|&v| v == depot_idx).unwrap();
+        giant_tour.rotate_left(depot_position);
+        giant_tour.remove(0);
+
+        let mut routes: Vec<Vec<usize>> = self.mtsp_split(depot_idx, &giant_tour, salesmen, objective)?;
+        let costs = |routes: &Vec<Vec<usize>>| -> (u64, u64) { objective.evaluate(routes.iter().map(|r| self.get_vehicle_route_cost(depot_idx, r))) };
+
+        if log {
+            println!("Split objective: {}", costs(&routes).0);
+        }
+
+        self.improve_vehicle_routes(depot_idx, &mut routes, objective, |_| true);
+
+        let end_time = Instant::now();
+
+        if log {
+            println!("Time elapsed: {} - Objective: {} - Salesmen used: {}", end_time.duration_since(start_time).as_micros(), costs(&routes).0, routes.iter().filter(|r| !r.is_empty()).count());
+        }
+
+        Ok(routes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::random_graph;
+    use itertools::Itertools;
+
+    fn evaluate(t: &Graph<usize>, depot_idx: usize, routes: &[Vec<usize>], objective: FleetObjective) -> (u64, u64) {
+        objective.evaluate(routes.iter().map(|r| t.get_vehicle_route_cost(depot_idx, r)))
+    }
+
+    // Melhor divisao em ate `salesmen` trechos testando todos os conjuntos de cortes
+    fn brute_force_split(t: &Graph<usize>, depot_idx: usize, giant_tour: &[usize], salesmen: usize, objective: FleetObjective) -> (u64, u64) {
+        let k: usize = giant_tour.len();
+
+        (0..1usize << k.saturating_sub(1))
+            .filter(|mask: &usize| (mask.count_ones() as usize) < salesmen)
+            .map(|mask| {
+                let mut routes: Vec<Vec<usize>> = vec![Vec::new()];
+
+                for (i, &v) in giant_tour.iter().enumerate() {
+                    if i > 0 && mask & (1 << (i - 1)) != 0 {
+                        routes.push(Vec::new());
+                    }
+
+                    routes.last_mut().unwrap().push(v);
+                }
+
+                evaluate(t, depot_idx, &routes, objective)
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn split_matches_brute_force() {
+        let mut seed: u64 = 47;
+
+        for n in [2, 3, 5, 8] {
+            for symmetric in [true, false] {
+                let t: Graph<usize> = random_graph(n, symmetric, &mut seed);
+                let giant_tour: Vec<usize> = (1..n).collect();
+
+                for salesmen in [1, 2, 3] {
+                    for objective in [FleetObjective::MinSum, FleetObjective::MinMax] {
+                        let routes: Vec<Vec<usize>> = t.mtsp_split(0, &giant_tour, salesmen, objective).unwrap();
+
+                        assert_eq!(routes.len(), salesmen);
+                        assert_eq!(routes.concat(), giant_tour);
+                        assert_eq!(evaluate(&t, 0, &routes, objective), brute_force_split(&t, 0, &giant_tour, salesmen, objective));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn approx_returns_valid_solutions() {
+        let mut seed: u64 = 48;
+
+        for n in [3, 5, 7] {
+            for symmetric in [true, false] {
+                let t: Graph<usize> = random_graph(n, symmetric, &mut seed);
+
+                for salesmen in [1, 2, 4] {
+                    for objective in [FleetObjective::MinSum, FleetObjective::MinMax] {
+                        // Toda solucao é a divisao de alguma ordem das cidades
+                        let optimum: (u64, u64) = (1..n).permutations(n - 1).map(|p| brute_force_split(&t, 0, &p, salesmen, objective)).min().unwrap();
+                        let routes: Vec<Vec<usize>> = t.mtsp_approx(0, salesmen, objective, false).unwrap();
+
+                        assert_eq!(routes.len(), salesmen);
+                        assert!(routes.iter().flatten().copied().sorted().eq(1..n));
+                        assert!(evaluate(&t, 0, &routes, objective) >= optimum);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn min_max_spreads_the_cities() {
+        // Duas cidades longe do deposito e em lados opostos: com min-sum um caixeiro basta, com min-max cada um
+        // pega uma cidade
+        let t: Graph<usize> = Graph::from((0..3).collect(), vec![vec![0, 10, 10], vec![10, 0, 1], vec![10, 1, 0]]);
+        let min_sum: Vec<Vec<usize>> = t.mtsp_approx(0, 2, FleetObjective::MinSum, false).unwrap();
+        let min_max: Vec<Vec<usize>> = t.mtsp_approx(0, 2, FleetObjective::MinMax, false).unwrap();
+
+        assert_eq!(evaluate(&t, 0, &min_sum, FleetObjective::MinSum), (21, 21));
+        assert_eq!(evaluate(&t, 0, &min_max, FleetObjective::MinMax), (20, 40));
+    }
+
+    #[test]
+    fn small_and_invalid_instances() {
+        let single: Graph<usize> = Graph::from(vec![0], vec![vec![0]]);
+        let pair: Graph<usize> = Graph::from(vec![0, 1], vec![vec![0, 3], vec![7, 0]]);
+
+        assert_eq!(single.mtsp_approx(0, 2, FleetObjective::MinSum, false), Ok(vec![vec![], vec![]]));
+        assert_eq!(pair.mtsp_approx(1, 3, FleetObjective::MinMax, false), Ok(vec![vec![0], vec![], vec![]]));
+
+        assert!(pair.mtsp_approx(0, 0, FleetObjective::MinSum, false).is_err());
+        assert!(pair.mtsp_approx(2, 1, FleetObjective::MinSum, false).is_err());
+        assert!(pair.mtsp_split(0, &[1], 0, FleetObjective::MinSum).is_err());
+
+        // A rota gigante precisa ser uma permutacao das cidades sem o deposito
+        let triangle: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0, 1, 2], vec![1, 0, 3], vec![2, 3, 0]]);
+        assert!(triangle.mtsp_split(3, &[1, 2], 1, FleetObjective::MinSum).is_err());
+        assert!(triangle.mtsp_split(0, &[1], 1, FleetObjective::MinSum).is_err());
+        assert!(triangle.mtsp_split(0, &[1, 1], 1, FleetObjective::MinSum).is_err());
+        assert!(triangle.mtsp_split(0, &[0, 1], 1, FleetObjective::MinSum).is_err());
+        assert!(triangle.mtsp_split(0, &[1, 3], 1, FleetObjective::MinSum).is_err());
+        assert_eq!(triangle.mtsp_split(0, &[2, 1], 1, FleetObjective::MinSum), Ok(vec![vec![2, 1]]));
+    }
+}
@@ -0,0 +1,330 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use crate::graph::Graph;
+
+// Aresta de um multigrafo: o id é estavel enquanto a aresta existir, mesmo que outras sejam removidas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MultiEdge {
+    id: usize,
+    src: usize,
+    dst: usize,
+    weight: u64
+}
+
+impl MultiEdge {
+    pub fn get_id(&self) -> usize {
+        self.id
+    }
+
+    pub fn get_src(&self) -> usize {
+        self.src
+    }
+
+    pub fn get_dst(&self) -> usize {
+        self.dst
+    }
+
+    pub fn get_weight(&self) -> u64 {
+        self.weight
+    }
+
+    // Retorna a outra ponta da aresta a partir de uma das suas pontas
+    pub fn get_opposite(&self, vertex_idx: usize) -> usize {
+        if self.src == vertex_idx {
+            self.dst
+        } else {
+            self.src
+        }
+    }
+}
+
+// Multigrafo nao direcionado: a mesma dupla de vertices pode ter varias arestas (ex: MST + emparelhamento
+// no Christofides). As arestas ficam num multiconjunto indexado pelo id.
+pub struct Multigraph<V: Eq + PartialEq + Hash + Display + Clone> {
+    vertices: Vec<V>,
+    edges: Vec<Option<MultiEdge>>,
+    // Ids das arestas incidentes em cada vertice (lacos aparecem duas vezes)
+    incidence: Vec<Vec<usize>>,
+    num_edges: usize
+}
+
+impl<V: Eq + Hash + Display + Clone> Default for Multigraph<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone> Multigraph<V> {
+    pub fn new() -> Self {
+        Multigraph {
+            vertices: Vec::<V>::new(),
+            edges: Vec::<Option<MultiEdge>>::new(),
+            incidence: Vec::<Vec<usize>>::new(),
+            num_edges: 0
+        }
+    }
+
+    // Cria o multigrafo com os vertices do grafo e uma aresta para cada par {i, j} com peso nao nulo.
+    // Um par que aparece só em uma direcao da matriz (como na arvore gerada pelo Prim) também vira aresta.
+    pub fn from_graph(graph: &Graph<V>) -> Result<Self, &'static str> {
+        let mut multigraph: Multigraph<V> = Multigraph::new();
+
+        for i in 0..graph.num_vertices() {
+            multigraph.insert_vertex(graph.get_vertex(i)?.clone());
+        }
+
+        for edge in graph.undirected_edges() {
+            multigraph.insert_edge(*edge.get_src(), *edge.get_dst(), edge.get_weight())?;
+        }
+
+        Ok(multigraph)
+    }
+
+    // Igual ao `from_graph`, mas adiciona as arestas extras (src, dst, peso) por cima das do grafo
+    pub fn from_graph_with_edges(graph: &Graph<V>, extra_edges: &[(usize, usize, u64)]) -> Result<Self, &'static str> {
+        let mut multigraph: Multigraph<V> = Multigraph::from_graph(graph)?;
+
+        for &(src, dst, weight) in extra_edges {
+            multigraph.insert_edge(src, dst, weight)?;
+        }
+
+        Ok(multigraph)
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.num_edges
+    }
+
+    pub fn get_vertex(&self, vertex_idx: usize) -> Result<&V, &'static str> {
+        if self.vertices.len() <= vertex_idx {
+            return Err("Index out of range!");
+        }
+
+        Ok(&self.vertices[vertex_idx])
+    }
+
+    pub fn insert_vertex(&mut self, vertex: V) -> usize {
+        self.vertices.push(vertex);
+        self.incidence.push(Vec::new());
+
+        self.vertices.len() - 1
+    }
+
+    // Insere uma nova aresta e retorna o seu id
+    pub fn insert_edge(&mut self, src_idx: usize, dst_idx: usize, edge_weight: u64) -> Result<usize, &'static str> {
+        if self.vertices.len() <= src_idx || self.vertices.len() <= dst_idx {
+            return Err("Index out of range!");
+        }
+
+        let id: usize = self.edges.len();
+
+        self.edges.push(Some(MultiEdge { id, src: src_idx, dst: dst_idx, weight: edge_weight }));
+        self.incidence[src_idx].push(id);
+        self.incidence[dst_idx].push(id);
+        self.num_edges += 1;
+
+        Ok(id)
+    }
+
+    pub fn remove_edge(&mut self, edge_id: usize) -> Result<MultiEdge, &'static str> {
+        let edge: MultiEdge = match self.edges.get_mut(edge_id).and_then(|e| e.take()) {
+            Some(e) => e,
+            None => return Err("Edge not found!")
+        };
+
+        self.incidence[edge.src].retain(|&id| id != edge_id);
+        self.incidence[edge.dst].retain(|&id| id != edge_id);
+        self.num_edges -= 1;
+
+        Ok(edge)
+    }
+
+    pub fn get_edge(&self, edge_id: usize) -> Result<&MultiEdge, &'static str> {
+        match self.edges.get(edge_id) {
+            Some(Some(edge)) => Ok(edge),
+            _ => Err("Edge not found!")
+        }
+    }
+
+    // Todas as arestas, cada copia de um par aparece separadamente
+    pub fn edges(&self) -> impl Iterator<Item = &MultiEdge> + '_ {
+        self.edges.iter().flatten()
+    }
+
+    // Arestas incidentes no vertice; um laco aparece duas vezes, uma para cada ponta
+    pub fn incident_edges(&self, vertex_idx: usize) -> Result<impl Iterator<Item = &MultiEdge> + '_, &'static str> {
+        if self.vertices.len() <= vertex_idx {
+            return Err("Index out of range!");
+        }
+
+        Ok(self.incidence[vertex_idx].iter().filter_map(move |&id| self.edges[id].as_ref()))
+    }
+
+    // Grau contando a multiplicidade das arestas (laco conta duas vezes)
+    pub fn degree(&self, vertex_idx: usize) -> Result<usize, &'static str> {
+        if self.vertices.len() <= vertex_idx {
+            return Err("Index out of range!");
+        }
+
+        Ok(self.incidence[vertex_idx].len())
+    }
+
+    // Quantas arestas ligam os dois vertices
+    pub fn multiplicity(&self, src_idx: usize, dst_idx: usize) -> Result<usize, &'static str> {
+        if self.vertices.len() <= src_idx || self.vertices.len() <= dst_idx {
+            return Err("Index out of range!");
+        }
+
+        let count: usize = self.incident_edges(src_idx)?
+            .filter(|e| e.get_opposite(src_idx) == dst_idx)
+            .count();
+
+        // Lacos foram contados uma vez para cada ponta
+        if src_idx == dst_idx {
+            return Ok(count / 2);
+        }
+
+        Ok(count)
+    }
+
+    pub fn total_weight(&self) -> u64 {
+        self.edges().map(|e| e.weight).sum()
+    }
+
+    pub fn get_odd_degree_vertices(&self) -> Vec<usize> {
+        (0..self.num_vertices()).filter(|&v| !self.incidence[v].len().is_multiple_of(2)).collect()
+    }
+
+    // Algoritmo de Hierholzer: https://en.wikipedia.org/wiki/Eulerian_path#Hierholzer's_algorithm
+    // Retorna o circuito como sequencia de vertices, o primeiro vertice se repete no final.
+    pub fn get_eulerian_circuit(&self, start_idx: usize) -> Result<Vec<usize>, &'static str> {
+        if self.vertices.len() <= start_idx {
+            return Err("Index out of range!");
+        }
+
+        if !self.get_odd_degree_vertices().is_empty() {
+            return Err("Graph does not have an eulerian circuit!");
+        }
+
+        let mut used: Vec<bool> = vec![false; self.edges.len()];
+        // Proxima posicao a olhar na lista de incidencia de cada vertice
+        let mut next: Vec<usize> = vec![0; self.num_vertices()];
+        let mut stack: Vec<usize> = vec![start_idx];
+        let mut circuit: Vec<usize> = Vec::with_capacity(self.num_edges + 1);
+
+        while let Some(&vertex_idx) = stack.last() {
+            let incident: &Vec<usize> = &self.incidence[vertex_idx];
+
+            while next[vertex_idx] < incident.len() && used[incident[next[vertex_idx]]] {
+                next[vertex_idx] += 1;
+            }
+
+            if next[vertex_idx] == incident.len() {
+                circuit.push(vertex_idx);
+                stack.pop();
+                continue;
+            }
+
+            let edge_id: usize = incident[next[vertex_idx]];
+            used[edge_id] = true;
+            stack.push(self.edges[edge_id].unwrap().get_opposite(vertex_idx));
+        }
+
+        // Sobrou aresta sem usar: ela esta em outra componente
+        if circuit.len() != self.num_edges + 1 {
+            return Err("Graph is not connected!");
+        }
+
+        circuit.reverse();
+
+        Ok(circuit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle() -> Multigraph<&'static str> {
+        let mut multigraph: Multigraph<&str> = Multigraph::new();
+
+        for v in ["a", "b", "c"] {
+            multigraph.insert_vertex(v);
+        }
+
+        multigraph
+    }
+
+    #[test]
+    fn parallel_edges_keep_their_multiplicity() {
+        let mut multigraph: Multigraph<&str> = triangle();
+        let first: usize = multigraph.insert_edge(0, 1, 3).unwrap();
+        let second: usize = multigraph.insert_edge(1, 0, 5).unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(multigraph.num_edges(), 2);
+        assert_eq!(multigraph.multiplicity(0, 1), Ok(2));
+        assert_eq!(multigraph.multiplicity(1, 0), Ok(2));
+        assert_eq!(multigraph.degree(0), Ok(2));
+        assert_eq!(multigraph.total_weight(), 8);
+        assert_eq!(multigraph.incident_edges(1).unwrap().map(|e| e.get_weight()).collect::<Vec<u64>>(), vec![3, 5]);
+        assert!(multigraph.get_odd_degree_vertices().is_empty());
+    }
+
+    #[test]
+    fn self_loops_count_twice() {
+        let mut multigraph: Multigraph<&str> = triangle();
+        let id: usize = multigraph.insert_edge(2, 2, 7).unwrap();
+
+        assert_eq!(multigraph.degree(2), Ok(2));
+        assert_eq!(multigraph.multiplicity(2, 2), Ok(1));
+        assert_eq!(multigraph.incident_edges(2).unwrap().count(), 2);
+        assert_eq!(multigraph.get_edge(id).unwrap().get_opposite(2), 2);
+        assert_eq!(multigraph.get_eulerian_circuit(2), Ok(vec![2, 2]));
+    }
+
+    #[test]
+    fn removal_keeps_the_other_ids() {
+        let mut multigraph: Multigraph<&str> = triangle();
+        let ids: Vec<usize> = [(0, 1), (1, 2), (2, 0), (0, 1)].iter().map(|&(u, v)| multigraph.insert_edge(u, v, 1).unwrap()).collect();
+
+        assert_eq!(multigraph.remove_edge(ids[3]).map(|e| (e.get_src(), e.get_dst())), Ok((0, 1)));
+        assert!(multigraph.remove_edge(ids[3]).is_err());
+        assert!(multigraph.get_edge(ids[3]).is_err());
+        assert_eq!(multigraph.get_edge(ids[2]).map(|e| e.get_id()), Ok(ids[2]));
+        assert_eq!(multigraph.num_edges(), 3);
+        assert_eq!(multigraph.multiplicity(0, 1), Ok(1));
+        assert_eq!(multigraph.get_eulerian_circuit(0), Ok(vec![0, 1, 2, 0]));
+
+        multigraph.remove_edge(ids[1]).unwrap();
+        assert_eq!(multigraph.get_odd_degree_vertices(), vec![1, 2]);
+        assert!(multigraph.get_eulerian_circuit(0).is_err());
+    }
+
+    #[test]
+    fn from_graph_with_extra_edges() {
+        let graph: Graph<&str> = Graph::from(vec!["a", "b", "c"], vec![vec![0, 2, 0], vec![0, 0, 4], vec![0, 0, 0]]);
+        let multigraph: Multigraph<&str> = Multigraph::from_graph_with_edges(&graph, &[(0, 1, 2), (2, 0, 6)]).unwrap();
+
+        assert_eq!(multigraph.num_edges(), 4);
+        assert_eq!(multigraph.multiplicity(0, 1), Ok(2));
+        assert_eq!(multigraph.get_odd_degree_vertices(), vec![0, 1]);
+        assert!(Multigraph::from_graph_with_edges(&graph, &[(0, 3, 1)]).is_err());
+    }
+
+    #[test]
+    fn invalid_indices() {
+        let mut multigraph: Multigraph<&str> = triangle();
+
+        assert!(multigraph.incident_edges(3).is_err());
+        assert!(multigraph.degree(3).is_err());
+        assert!(multigraph.multiplicity(0, 3).is_err());
+        assert!(multigraph.insert_edge(3, 0, 1).is_err());
+        assert!(multigraph.get_vertex(3).is_err());
+        assert!(multigraph.get_eulerian_circuit(3).is_err());
+    }
+}
@@ -0,0 +1,445 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::time::Instant;
+use itertools::Itertools;
+use crate::graph::Graph;
+use crate::solver::Solver;
+
+// Maior grafo aceito pelo Held-Karp: a tabela tem 2^(n-1)·(n-1) estados
+const HELD_KARP_MAX_VERTICES: usize = 20;
+
+// O que a rota otimiza. A rota sai de route[0] e volta a ele; o valor de cada objetivo soma os arcos de um jeito:
+// - Length: comprimento total (o TSP normal)
+// - Bottleneck: o arco mais caro, a ser minimizado
+// - MaxLength: comprimento total, a ser maximizado (TSP maximo)
+// - Latency: soma dos tempos de chegada em cada cidade a partir de route[0], sem contar a volta (minimum latency)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Objective {
+    #[default]
+    Length,
+    Bottleneck,
+    MaxLength,
+    Latency
+}
+
+impl Objective {
+    pub const ALL: [Objective; 4] = [
+        Objective::Length,
+        Objective::Bottleneck,
+        Objective::MaxLength,
+        Objective::Latency
+    ];
+
+    pub fn from_name(name: &str) -> Option<Objective> {
+        Objective::ALL.into_iter().find(|objective| objective.get_name() == name)
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Objective::Length => "length",
+            Objective::Bottleneck => "bottleneck",
+            Objective::MaxLength => "max-length",
+            Objective::Latency => "latency"
+        }
+    }
+
+    // Acrescenta um arco ao valor acumulado. `remaining` é quantas cidades chegam depois dele (e contam o tempo
+    // dele na latencia): n - 1 - i para o i-esimo arco, 0 para a volta ao inicio.
+    pub fn step(&self, accumulated: u64, weight: u64, remaining: u64) -> u64 {
+        match self {
+            Objective::Length | Objective::MaxLength => accumulated + weight,
+            Objective::Bottleneck => accumulated.max(weight),
+            Objective::Latency => accumulated + weight * remaining
+        }
+    }
+
+    // Se o valor a é melhor que b
+    pub fn is_better(&self, a: u64, b: u64) -> bool {
+        match self {
+            Objective::MaxLength => a > b,
+            _ => a < b
+        }
+    }
+
+    // Chave de comparacao da busca local, menor é melhor: o objetivo e, para desempatar, o comprimento. Sem o
+    // desempate o gargalo fica num plato, ja que quase nenhum movimento muda o arco mais caro.
+    pub fn key(&self, value: u64, length: u64) -> (u64, u64) {
+        match self {
+            Objective::Length => (value, 0),
+            Objective::MaxLength => (u64::MAX - value, 0),
+            Objective::Bottleneck | Objective::Latency => (value, length)
+        }
+    }
+}
+
+impl Display for Objective {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.get_name())
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Valor da rota no objetivo, comecando em route[0]
+    pub fn get_route_objective(&self, route: &[usize], objective: Objective) -> Result<u64, &'static str> {
+        if !self.is_squared() {
+            return Err("Graph is not squared!");
+        }
+
+        if route.is_empty() {
+            return Err("Route is empty!");
+        }
+
+        if route.iter().any(|&v| self.num_vertices() <= v) {
+            return Err("Index out of range!");
+        }
+
+        Ok(self.route_objective(route, objective))
+    }
+
+    fn route_objective(&self, route: &[usize], objective: Objective) -> u64 {
+        let n: usize = route.len();
+
+        (0..n).fold(0, |value, i| objective.step(value, self.weight(route[i], route[(i + 1) % n]), (n - 1 - i) as u64))
+    }
+
+    fn route_objective_key(&self, route: &[usize], objective: Objective) -> (u64, u64) {
+        objective.key(self.route_objective(route, objective), self.route_objective(route, Objective::Length))
+    }
+
+    // Complemento dos pesos, max + 1 - d(i, j): o comprimento de qualquer rota vira n·(max + 1) menos o original,
+    // entao a rota mais curta do complemento é a mais longa deste grafo
+    fn get_max_length_complement(&self) -> Graph<usize> {
+        let n: usize = self.num_vertices();
+        let max_weight: u64 = (0..n).flat_map(|i| (0..n).map(move |j| (i, j))).map(|(i, j)| self.weight(i, j)).max().unwrap_or(0);
+        let matrix: Vec<Vec<u64>> = (0..n).map(|i| (0..n).map(|j| if i == j { 0 } else { max_weight + 1 - self.weight(i, j) }).collect()).collect();
+
+        Graph::from((0..n).collect(), matrix)
+    }
+
+    fn check_objective_instance(&self) -> Result<(), &'static str> {
+        self.check_complete()?;
+
+        if self.num_vertices() == 0 {
+            return Err("Graph is empty!");
+        }
+
+        Ok(())
+    }
+
+    // Forca bruta para qualquer objetivo. O vertice 0 fica fixo no inicio, ja que a latencia depende de onde a
+    // rota comeca.
+    pub fn tsp_brute_force_with_objective(&self, objective: Objective, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        self.check_objective_instance()?;
+
+        let start_time = Instant::now();
+
+        let n: usize = self.num_vertices();
+        let mut best_route: Vec<usize> = (0..n).collect();
+        let mut best_value: u64 = self.route_objective(&best_route, objective);
+        let mut counter: usize = 0;
+
+        for permutation in (1..n).permutations(n - 1) {
+            counter += 1;
+
+            let route: Vec<usize> = [0].into_iter().chain(permutation).collect();
+            let value: u64 = self.route_objective(&route, objective);
+
+            if objective.is_better(value, best_value) {
+                best_value = value;
+                best_route = route;
+            }
+
+            if log && counter.is_multiple_of(10000) {
+                println!("Iteration: {} - Time elapsed: {} - Route {}: {}", counter, Instant::now().duration_since(start_time).as_micros(), objective, best_value);
+            }
+        }
+
+        let end_time = Instant::now();
+
+        if log {
+            println!("Total iterations: {} - Time elapsed: {} - Best route {}: {} - Best route found: {}", counter, end_time.duration_since(start_time).as_micros(), objective, best_value, best_route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
+        }
+
+        Ok((best_route, best_value))
+    }
+
+    // Held-Karp: programacao dinamica sobre subconjuntos, O(2^n·n²). best[S][j] é o melhor valor de um caminho que
+    // sai de 0, visita exatamente os vertices de S e termina em j. Funciona para todos os objetivos porque o
+    // custo de um arco so depende de quantos vertices ja foram visitados (o `remaining` da latencia).
+    pub fn tsp_held_karp(&self, objective: Objective, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        self.check_objective_instance()?;
+
+        if self.num_vertices() > HELD_KARP_MAX_VERTICES {
+            return Err("Graph is too large for Held-Karp!");
+        }
+
+        let start_time = Instant::now();
+
+        let n: usize = self.num_vertices();
+
+        if n == 1 {
+            return Ok((vec![0], 0));
+        }
+
+        // Os vertices 1..n viram os bits 0..n-1 dos subconjuntos
+        let k: usize = n - 1;
+        let full: usize = (1 << k) - 1;
+        let mut best: Vec<u64> = vec![0; (1 << k) * k];
+        let mut parent: Vec<u8> = vec![0; (1 << k) * k];
+
+        for subset in 1..=full {
+            let visited: u64 = subset.count_ones() as u64;
+
+            for j in (0..k).filter(|&j| subset & (1 << j) != 0) {
+                let rest: usize = subset & !(1 << j);
+                let remaining: u64 = n as u64 - visited;
+
+                if rest == 0 {
+                    best[subset * k + j] = objective.step(0, self.weight(0, j + 1), remaining);
+                    continue;
+                }
+
+                let (value, i) = (0..k)
+                    .filter(|&i| rest & (1 << i) != 0)
+                    .map(|i| (objective.step(best[rest * k + i], self.weight(i + 1, j + 1), remaining), i))
+                    .reduce(|a, b| if objective.is_better(b.0, a.0) { b } else { a })
+                    .unwrap();
+
+                best[subset * k + j] = value;
+                parent[subset * k + j] = i as u8;
+            }
+        }
+
+        let (best_value, mut last) = (0..k)
+            .map(|j| (objective.step(best[full * k + j], self.weight(j + 1, 0), 0), j))
+            .reduce(|a, b| if objective.is_better(b.0, a.0) { b } else { a })
+            .unwrap();
+
+        let mut route: Vec<usize> = Vec::with_capacity(n);
+        let mut subset: usize = full;
+
+        while subset != 0 {
+            route.push(last + 1);
+            let previous: usize = parent[subset * k + last] as usize;
+            subset &= !(1 << last);
+            last = previous;
+        }
+
+        route.push(0);
+        route.reverse();
+
+        let end_time = Instant::now();
+
+        if log {
+            println!("Time elapsed: {} - States: {} - Route {}: {} - Route found: {}", end_time.duration_since(start_time).as_micros(), (1usize << k) * k, objective, best_value, route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
+        }
+
+        Ok((route, best_value))
+    }
+
+    // Busca local para qualquer objetivo, com route[0] fixo: move um segmento de 1 a 3 vertices, troca dois
+    // vertices ou inverte um trecho. Cada candidato é avaliado por inteiro (O(n)), entao cada passada custa O(n³).
+    // Aplica a primeira melhora encontrada ate nao haver mais; retorna se a rota mudou.
+    pub fn improve_route_objective(&self, route: &mut Vec<usize>, objective: Objective) -> bool {
+        let n: usize = route.len();
+        let mut best_key: (u64, u64) = self.route_objective_key(route, objective);
+        let mut improved: bool = false;
+
+        'search: loop {
+            for segment_len in 1..=3 {
+                for i in 1..n.saturating_sub(segment_len - 1) {
+                    let mut rest: Vec<usize> = route.clone();
+                    let segment: Vec<usize> = rest.drain(i..i + segment_len).collect();
+
+                    for position in (1..=rest.len()).filter(|&p| p != i) {
+                        let mut candidate: Vec<usize> = rest.clone();
+                        candidate.splice(position..position, segment.iter().copied());
+                        let key: (u64, u64) = self.route_objective_key(&candidate, objective);
+
+                        if key < best_key {
+                            best_key = key;
+                            *route = candidate;
+                            improved = true;
+                            continue 'search;
+                        }
+                    }
+                }
+            }
+
+            for i in 1..n {
+                for j in i + 1..n {
+                    route.swap(i, j);
+
+                    let key: (u64, u64) = self.route_objective_key(route, objective);
+
+                    if key < best_key {
+                        best_key = key;
+                        improved = true;
+                        continue 'search;
+                    }
+
+                    route.swap(i, j);
+                    route[i..=j].reverse();
+
+                    let key: (u64, u64) = self.route_objective_key(route, objective);
+
+                    if key < best_key {
+                        best_key = key;
+                        improved = true;
+                        continue 'search;
+                    }
+
+                    route[i..=j].reverse();
+                }
+            }
+
+            return improved;
+        }
+    }
+
+    // Resolve o TSP com o objetivo escolhido. Forca bruta e Held-Karp otimizam o objetivo diretamente; os demais
+    // algoritmos dao a rota inicial (no TSP maximo, a mais curta do complemento dos pesos), que é melhorada pela
+    // busca local do objetivo. A rota retornada comeca no vertice 0.
+    pub fn solve_with_objective(&self, solver: Solver, objective: Objective, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        match (solver, objective) {
+            (_, Objective::Length) => return self.solve(solver, log),
+            (Solver::BruteForce, _) => return self.tsp_brute_force_with_objective(objective, log),
+            (Solver::HeldKarp, _) => return self.tsp_held_karp(objective, log),
+            _ => {}
+        }
+
+        self.check_objective_instance()?;
+
+        let start_time = Instant::now();
+
+        let (mut route, _) = if objective == Objective::MaxLength {
+            self.get_max_length_complement().solve(solver, false)?
+        } else {
+            self.solve(solver, false)?
+        };
+
+        let first_position: usize = route.iter().position(|&v| v == 0).unwrap();
+        route.rotate_left(first_position);
+
+        if log {
+            println!("{} route {}: {}", solver, objective, self.route_objective(&route, objective));
+        }
+
+        self.improve_route_objective(&mut route, objective);
+
+        let value: u64 = self.route_objective(&route, objective);
+
+        let end_time = Instant::now();
+
+        if log {
+            println!("Time elapsed: {} - Route {}: {} - Route found: {}", end_time.duration_since(start_time).as_micros(), objective, value, route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
+        }
+
+        Ok((route, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::random_graph;
+    use crate::coordinates::{Coordinates, Metric};
+
+    #[test]
+    fn names_round_trip() {
+        for objective in Objective::ALL {
+            assert_eq!(Objective::from_name(&objective.to_string()), Some(objective));
+        }
+
+        assert_eq!(Objective::from_name("makespan"), None);
+        assert_eq!(Objective::default(), Objective::Length);
+    }
+
+    #[test]
+    fn route_objective_known_answer() {
+        let t: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0, 1, 5], vec![2, 0, 3], vec![4, 6, 0]]);
+        let values = |route: &[usize]| -> Vec<u64> { Objective::ALL.iter().map(|&o| t.get_route_objective(route, o).unwrap()).collect() };
+
+        // Arcos 1, 3 e 4; na latencia o primeiro conta para duas cidades, o segundo para uma e a volta para nenhuma
+        assert_eq!(values(&[0, 1, 2]), vec![8, 4, 8, 2 + 3]);
+        assert_eq!(values(&[1, 2, 0]), vec![8, 4, 8, 3 * 2 + 4]);
+        assert_eq!(values(&[0, 2, 1]), vec![13, 6, 13, 5 * 2 + 6]);
+
+        assert!(t.get_route_objective(&[], Objective::Length).is_err());
+        assert!(t.get_route_objective(&[0, 3], Objective::Length).is_err());
+    }
+
+    #[test]
+    fn held_karp_matches_brute_force() {
+        let mut seed: u64 = 53;
+
+        for n in 1..=8 {
+            for symmetric in [true, false] {
+                let t: Graph<usize> = random_graph(n, symmetric, &mut seed);
+
+                for objective in Objective::ALL {
+                    let (route, value) = t.tsp_held_karp(objective, false).unwrap();
+                    let (brute_route, brute_value) = t.tsp_brute_force_with_objective(objective, false).unwrap();
+
+                    assert_eq!(route[0], 0);
+                    assert_eq!(brute_route[0], 0);
+                    assert!(route.iter().copied().sorted().eq(0..n));
+                    assert_eq!(t.get_route_objective(&route, objective), Ok(value));
+                    assert_eq!(value, brute_value, "{} {}", objective, n);
+                }
+
+                assert_eq!(t.tsp_held_karp(Objective::Length, false).unwrap().1, t.tsp_brute_force(false).unwrap().1);
+            }
+        }
+    }
+
+    #[test]
+    fn heuristics_are_never_better_than_the_optimum() {
+        let mut seed: u64 = 54;
+
+        for n in [3, 5, 7] {
+            let t: Graph<usize> = random_graph(n, false, &mut seed);
+
+            for objective in Objective::ALL {
+                let optimum: u64 = t.tsp_held_karp(objective, false).unwrap().1;
+
+                for solver in [Solver::NearestNeighbor, Solver::CheapestInsertion, Solver::OrOpt] {
+                    let (route, value) = t.solve_with_objective(solver, objective, false).unwrap();
+
+                    assert!(route.iter().copied().sorted().eq(0..n));
+                    assert_eq!(t.get_route_objective(&route, objective), Ok(value));
+                    assert!(!objective.is_better(value, optimum), "{} {}", solver, objective);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn small_graphs() {
+        let single: Graph<usize> = Graph::from(vec![0], vec![vec![0]]);
+        let pair: Graph<usize> = Graph::from(vec![0, 1], vec![vec![0, 3], vec![7, 0]]);
+
+        for (objective, expected) in Objective::ALL.into_iter().zip([10, 7, 10, 3]) {
+            assert_eq!(single.tsp_held_karp(objective, false), Ok((vec![0], 0)));
+            assert_eq!(single.tsp_brute_force_with_objective(objective, false), Ok((vec![0], 0)));
+            assert_eq!(pair.tsp_held_karp(objective, false), Ok((vec![0, 1], expected)));
+            assert_eq!(pair.tsp_brute_force_with_objective(objective, false), Ok((vec![0, 1], expected)));
+        }
+
+        assert!(Graph::<usize>::new().tsp_held_karp(Objective::Length, false).is_err());
+    }
+
+    #[test]
+    fn zero_weights() {
+        // Pontos coincidentes nas coordenadas ficam ligados, com distancia 0, e nao contam como falta de aresta
+        let coordinates: Coordinates = Coordinates::new(vec![(0.0, 0.0), (0.0, 0.0), (3.0, 4.0)], Metric::Euclidean);
+        let t: Graph<usize> = Graph::from_coordinates(vec![0, 1, 2], coordinates).unwrap();
+
+        for (objective, expected) in Objective::ALL.into_iter().zip([10, 5, 10, 5]) {
+            assert_eq!(t.tsp_held_karp(objective, false).unwrap().1, expected);
+            assert_eq!(t.tsp_brute_force_with_objective(objective, false).unwrap().1, expected);
+        }
+
+        assert_eq!(t.tsp_held_karp(Objective::Latency, false).unwrap().0, vec![0, 1, 2]);
+    }
+}
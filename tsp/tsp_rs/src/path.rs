@@ -0,0 +1,226 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::time::Instant;
+use crate::graph::Graph;
+use crate::solver::Solver;
+
+// Forma da rota: um ciclo que volta ao inicio, ou um caminho aberto (sem o arco de volta) com a cidade
+// inicial e a final opcionalmente fixas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RouteMode {
+    #[default]
+    Cycle,
+    Path {
+        start: Option<usize>,
+        end: Option<usize>
+    }
+}
+
+impl RouteMode {
+    // Se a rota começa e termina onde o modo exige
+    pub fn accepts(&self, route: &[usize]) -> bool {
+        match self {
+            RouteMode::Cycle => true,
+            RouteMode::Path { start, end } => {
+                start.is_none_or(|s| route.first() == Some(&s)) && end.is_none_or(|e| route.last() == Some(&e))
+            }
+        }
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Custo do caminho aberto: igual ao `get_route_cost`, mas sem o arco route[last] -> route[0]
+    pub fn get_path_cost(&self, route: &[usize]) -> Result<u64, &'static str> {
+        if !self.is_squared() {
+            return Err("Graph is not squared!");
+        }
+
+        let mut cost: u64 = 0;
+
+        for pair in route.windows(2) {
+            cost += self.get_edge_weight(pair[0], pair[1])?;
+        }
+
+        Ok(cost)
+    }
+
+    // Custo da rota no modo escolhido, verificando o inicio e o fim fixos
+    pub fn get_route_cost_with_mode(&self, route: &[usize], mode: RouteMode) -> Result<u64, &'static str> {
+        match mode {
+            RouteMode::Cycle => self.get_route_cost(route),
+            RouteMode::Path { .. } => {
+                if !mode.accepts(route) {
+                    return Err("Route does not start or end at the fixed vertices!");
+                }
+
+                self.get_path_cost(route)
+            }
+        }
+    }
+
+    // Grafo com uma cidade ficticia extra (indice n) ligando o fim do caminho ao inicio, de forma que um ciclo
+    // no grafo novo corresponde a um caminho aberto neste. Os arcos da cidade ficticia custam 1 quando o modo
+    // permite aquela ponta e `penalty` quando nao permite. Em grafos simetricos as ligacoes sao nao direcionadas
+    // e o sentido do caminho é escolhido depois, entao com uma ponta fixa toda rota paga exatamente uma penalidade.
+    fn get_path_graph(&self, start: Option<usize>, end: Option<usize>, penalty: u64) -> Graph<usize> {
+        let n: usize = self.num_vertices();
+        let symmetric: bool = self.is_symmetric();
+        let cost = |allowed: bool| -> u64 { if allowed { 1 } else { penalty } };
+
+        // Pesos dos arcos ficticio -> v e v -> ficticio
+        let dummy_weights = |v: usize| -> (u64, u64) {
+            if symmetric {
+                let weight: u64 = match (start, end) {
+                    (None, None) => 1,
+                    (Some(s), None) | (None, Some(s)) => cost(s == v),
+                    (Some(s), Some(e)) => cost(s == v || e == v)
+                };
+
+                (weight, weight)
+            } else {
+                (cost(start.is_none_or(|s| s == v)), cost(end.is_none_or(|e| e == v)))
+            }
+        };
+
+        let mut matrix: Vec<Vec<u64>> = (0..n).map(|i| (0..n).map(|j| self.weight(i, j)).chain([dummy_weights(i).1]).collect()).collect();
+        matrix.push((0..n).map(|v| dummy_weights(v).0).chain([0]).collect());
+
+        Graph::from((0..=n).collect(), matrix)
+    }
+
+    // Resolve o TSP no modo escolhido com qualquer algoritmo. Caminhos abertos viram ciclos com uma cidade
+    // ficticia (ver `get_path_graph`); se o algoritmo ainda assim nao respeitar as pontas fixas, elas sao
+    // movidas para o lugar e a rota é melhorada com or-opt, que nunca volta a usar um arco penalizado.
+    pub fn solve_with_mode(&self, solver: Solver, mode: RouteMode, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        let (start, end) = match mode {
+            RouteMode::Cycle => return self.solve(solver, log),
+            RouteMode::Path { start, end } => (start, end)
+        };
+
+        let n: usize = self.num_vertices();
+
+        if start.is_some_and(|s| n <= s) || end.is_some_and(|e| n <= e) {
+            return Err("Index out of range!");
+        }
+
+        if n > 1 && start.is_some() && start == end {
+            return Err("Start and end must be different!");
+        }
+
+        self.check_complete()?;
+
+        let max_weight: u64 = (0..n).flat_map(|i| (0..n).map(move |j| (i, j))).map(|(i, j)| self.weight(i, j)).max().unwrap_or(0);
+        let penalty: u64 = match max_weight.checked_mul(n as u64 + 1).and_then(|w| w.checked_add(2)) {
+            Some(p) => p,
+            None => return Err("Edge weights are too large for the transformation!")
+        };
+
+        let start_time = Instant::now();
+
+        let path_graph: Graph<usize> = self.get_path_graph(start, end, penalty);
+        let (tour, _) = path_graph.solve(solver, false)?;
+
+        // Abre o ciclo na cidade ficticia
+        let dummy_position: usize = tour.iter().position(|&v| v == n).unwrap();
+        let mut route: Vec<usize> = tour[dummy_position + 1..].iter().chain(tour[..dummy_position].iter()).copied().collect();
+
+        if !mode.accepts(&route) && self.is_symmetric() {
+            route.reverse();
+
+            if !mode.accepts(&route) {
+                route.reverse();
+            }
+        }
+
+        if !mode.accepts(&route) {
+            if let Some(s) = start {
+                route.retain(|&v| v != s);
+                route.insert(0, s);
+            }
+
+            if let Some(e) = end {
+                route.retain(|&v| v != e);
+                route.push(e);
+            }
+
+            let mut tour: Vec<usize> = [n].into_iter().chain(route).collect();
+            path_graph.improve_or_opt(&mut tour);
+
+            let dummy_position: usize = tour.iter().position(|&v| v == n).unwrap();
+            route = tour[dummy_position + 1..].iter().chain(tour[..dummy_position].iter()).copied().collect();
+        }
+
+        let cost: u64 = self.get_route_cost_with_mode(&route, mode)?;
+
+        let end_time = Instant::now();
+
+        if log {
+            println!("Time elapsed: {} - Solver: {} - Path cost: {} - Path found: {}", end_time.duration_since(start_time).as_micros(), solver, cost, route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
+        }
+
+        Ok((route, cost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::random_graph;
+    use itertools::Itertools;
+
+    // Menor caminho aberto entre todas as permutacoes aceitas pelo modo
+    fn brute_force_path(t: &Graph<usize>, mode: RouteMode) -> u64 {
+        let n: usize = t.num_vertices();
+
+        (0..n).permutations(n).filter(|p| mode.accepts(p)).map(|p| t.get_path_cost(&p).unwrap()).min().unwrap()
+    }
+
+    #[test]
+    fn path_known_answer() {
+        // Cidades numa linha: o melhor caminho aberto vai de uma ponta a outra
+        let t: Graph<usize> = Graph::from((0..4).collect(), (0..4).map(|i: u64| (0..4).map(|j: u64| i.abs_diff(j)).collect()).collect());
+        let free: RouteMode = RouteMode::Path { start: None, end: None };
+        let fixed: RouteMode = RouteMode::Path { start: Some(1), end: Some(2) };
+
+        assert_eq!(t.solve_with_mode(Solver::HeldKarp, free, false).unwrap().1, 3);
+        assert_eq!(t.solve_with_mode(Solver::HeldKarp, fixed, false), Ok((vec![1, 0, 3, 2], 5)));
+        assert_eq!(t.get_route_cost_with_mode(&[0, 1, 2, 3], RouteMode::Cycle), Ok(6));
+        assert!(t.get_route_cost_with_mode(&[0, 1, 2, 3], fixed).is_err());
+    }
+
+    #[test]
+    fn exact_paths_match_brute_force() {
+        let mut seed: u64 = 43;
+
+        for n in [2, 3, 5, 6] {
+            for symmetric in [true, false] {
+                let t: Graph<usize> = random_graph(n, symmetric, &mut seed);
+                let modes: [RouteMode; 4] = [
+                    RouteMode::Path { start: None, end: None },
+                    RouteMode::Path { start: Some(0), end: None },
+                    RouteMode::Path { start: None, end: Some(n - 1) },
+                    RouteMode::Path { start: Some(n - 1), end: Some(0) }
+                ];
+
+                for mode in modes {
+                    let (route, cost) = t.solve_with_mode(Solver::HeldKarp, mode, false).unwrap();
+
+                    assert!(mode.accepts(&route));
+                    assert_eq!(t.get_path_cost(&route), Ok(cost));
+                    assert_eq!(cost, brute_force_path(&t, mode));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn small_graphs() {
+        let single: Graph<usize> = Graph::from(vec![0], vec![vec![0]]);
+        let pair: Graph<usize> = Graph::from(vec![0, 1], vec![vec![0, 3], vec![7, 0]]);
+
+        assert_eq!(single.solve_with_mode(Solver::HeldKarp, RouteMode::Path { start: Some(0), end: Some(0) }, false), Ok((vec![0], 0)));
+        assert_eq!(pair.solve_with_mode(Solver::HeldKarp, RouteMode::Path { start: None, end: None }, false), Ok((vec![0, 1], 3)));
+        assert_eq!(pair.solve_with_mode(Solver::HeldKarp, RouteMode::Path { start: Some(1), end: None }, false), Ok((vec![1, 0], 7)));
+        assert!(pair.solve_with_mode(Solver::HeldKarp, RouteMode::Path { start: Some(1), end: Some(1) }, false).is_err());
+    }
+}
@@ -0,0 +1,305 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::time::Instant;
+use crate::graph::Graph;
+
+// Restricoes "visitar a antes de b", como coleta antes da entrega. As rotas saem do deposito, entao a ordem é
+// contada a partir dele: o deposito vem antes de todos e nao pode ser o segundo vertice de um par.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Precedences {
+    pairs: Vec<(usize, usize)>
+}
+
+impl Precedences {
+    pub fn new(pairs: Vec<(usize, usize)>) -> Self {
+        Precedences {
+            pairs
+        }
+    }
+
+    pub fn get_pairs(&self) -> &Vec<(usize, usize)> {
+        &self.pairs
+    }
+
+    // Vertices que precisam vir antes de cada vertice
+    fn get_predecessors(&self, num_vertices: usize) -> Result<Vec<Vec<usize>>, &'static str> {
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); num_vertices];
+
+        for &(before, after) in self.pairs.iter() {
+            if num_vertices <= before || num_vertices <= after {
+                return Err("Index out of range!");
+            }
+
+            predecessors[after].push(before);
+        }
+
+        Ok(predecessors)
+    }
+
+    // Um ciclo de restricoes, se existir, na ordem em que elas pedem (cada vertice antes do seguinte e o ultimo
+    // antes do primeiro). Nesse caso nenhuma rota é viavel.
+    pub fn get_cycle(&self, num_vertices: usize) -> Result<Option<Vec<usize>>, &'static str> {
+        let predecessors: Vec<Vec<usize>> = self.get_predecessors(num_vertices)?;
+        // 0: nao visitado, 1: na pilha da busca, 2: terminado
+        let mut state: Vec<u8> = vec![0; num_vertices];
+        let mut parent: Vec<usize> = vec![usize::MAX; num_vertices];
+
+        for root in 0..num_vertices {
+            if state[root] != 0 {
+                continue;
+            }
+
+            let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+            state[root] = 1;
+
+            while let Some(&(v, next)) = stack.last() {
+                if next == predecessors[v].len() {
+                    state[v] = 2;
+                    stack.pop();
+                    continue;
+                }
+
+                let u: usize = predecessors[v][next];
+                stack.last_mut().unwrap().1 += 1;
+
+                match state[u] {
+                    0 => {
+                        state[u] = 1;
+                        parent[u] = v;
+                        stack.push((u, 0));
+                    },
+                    1 => {
+                        // A busca segue os predecessores: u vem antes de v, v antes de parent[v] e assim por diante ate u
+                        let mut cycle: Vec<usize> = vec![u];
+                        let mut current: usize = v;
+
+                        while current != u {
+                            cycle.push(current);
+                            current = parent[current];
+                        }
+
+                        return Ok(Some(cycle));
+                    },
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Pares (a, b) em que b aparece antes de a na rota, contando a partir do deposito
+    pub fn get_violations(&self, route: &[usize], depot_idx: usize) -> Vec<(usize, usize)> {
+        let n: usize = route.len();
+        let start: usize = route.iter().position(|&v| v == depot_idx).unwrap_or(0);
+        let size: usize = route.iter().chain(self.pairs.iter().flat_map(|(before, after)| [before, after])).max().map_or(0, |&v| v + 1);
+        // Vertices fora da rota ficam com usize::MAX e violam todos os seus pares
+        let mut order: Vec<usize> = vec![usize::MAX; size];
+
+        for (i, &v) in route.iter().enumerate() {
+            order[v] = (i + n - start) % n;
+        }
+
+        self.pairs.iter()
+            .filter(|&&(before, after)| order[before] == usize::MAX || order[after] == usize::MAX || order[after] <= order[before])
+            .copied()
+            .collect()
+    }
+
+    pub fn is_satisfied(&self, route: &[usize], depot_idx: usize) -> bool {
+        self.get_violations(route, depot_idx).is_empty()
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Verifica a instancia e se existe rota viavel: sem ciclos de restricoes e sem vertice que precise vir antes
+    // do deposito
+    pub fn check_precedences(&self, depot_idx: usize, precedences: &Precedences) -> Result<(), &'static str> {
+        let n: usize = self.num_vertices();
+
+        if depot_idx >= n || precedences.pairs.iter().any(|&(before, after)| before >= n || after >= n) {
+            return Err("Index out of range!");
+        }
+
+        if precedences.pairs.iter().any(|&(_, after)| after == depot_idx) {
+            return Err("The depot cannot come after another vertex, no feasible tour exists!");
+        }
+
+        if precedences.get_cycle(n)?.is_some() {
+            return Err("Precedence constraints have a cycle, no feasible tour exists!");
+        }
+
+        Ok(())
+    }
+
+    // TSP com precedencias saindo do deposito. Constroi a rota por insercao mais barata, so inserindo vertices
+    // cujos predecessores ja estao na rota e sempre depois deles, o que garante uma rota viavel. Depois aplica
+    // 3-opt sem inversao e or-opt recusando os movimentos que quebram alguma precedencia. Retorna a rota
+    // comecando pelo deposito.
+    pub fn tsp_precedence(&self, depot_idx: usize, precedences: &Precedences, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        self.check_complete()?;
+
+        self.check_precedences(depot_idx, precedences)?;
+
+        let start_time = Instant::now();
+
+        let n: usize = self.num_vertices();
+        let w = |i: usize, j: usize| -> i64 { self.weight(i, j) as i64 };
+        let predecessors: Vec<Vec<usize>> = precedences.get_predecessors(n)?;
+
+        let mut route: Vec<usize> = vec![depot_idx];
+        let mut in_route: Vec<bool> = (0..n).map(|v| v == depot_idx).collect();
+
+        while route.len() < n {
+            let (_, v, position) = (0..n)
+                .filter(|&v| !in_route[v] && predecessors[v].iter().all(|&u| in_route[u]))
+                .flat_map(|v| {
+                    let first: usize = predecessors[v].iter().map(|&u| route.iter().position(|&x| x == u).unwrap() + 1).max().unwrap_or(1);
+                    (first..=route.len()).map(move |p| (v, p))
+                })
+                .map(|(v, p)| {
+                    let (a, b) = (route[p - 1], route[p % route.len()]);
+                    (w(a, v) + w(v, b) - w(a, b), v, p)
+                })
+                .min()
+                .unwrap();
+
+            route.insert(position, v);
+            in_route[v] = true;
+        }
+
+        if log {
+            println!("Insertion route cost: {}", self.get_route_cost(&route)?);
+        }
+
+        let accept = |candidate: &[usize]| -> bool { precedences.is_satisfied(candidate, depot_idx) };
+        let mut counter: usize = 0;
+
+        loop {
+            counter += 1;
+
+            let segment_improved: bool = self.improve_segment_insertion_with(&mut route, accept);
+            let or_opt_improved: bool = self.improve_or_opt_with(&mut route, accept);
+
+            if !segment_improved && !or_opt_improved {
+                break;
+            }
+        }
+
+        let depot_position: usize = route.iter().position(|&v| v == depot_idx).unwrap();
+        route.rotate_left(depot_position);
+
+        let cost: u64 = self.get_route_cost(&route)?;
+
+        let end_time = Instant::now();
+
+        if log {
+            println!("Time elapsed: {} - Total iterations: {} - Route cost: {} - Route found: {}", end_time.duration_since(start_time).as_micros(), counter, cost, route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
+        }
+
+        Ok((route, cost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{next_random, random_graph};
+    use itertools::Itertools;
+
+    #[test]
+    fn cycle_detection() {
+        let acyclic: Precedences = Precedences::new(vec![(1, 2), (1, 3), (2, 4), (3, 4)]);
+        let cyclic: Precedences = Precedences::new(vec![(0, 1), (1, 2), (2, 3), (3, 1)]);
+
+        assert_eq!(acyclic.get_cycle(5), Ok(None));
+        assert_eq!(Precedences::new(vec![(2, 2)]).get_cycle(3), Ok(Some(vec![2])));
+        assert!(acyclic.get_cycle(4).is_err());
+        assert!(Precedences::new(vec![(1, 99)]).get_cycle(11).is_err());
+
+        // Cada vertice do ciclo precisa vir antes do seguinte e o ultimo antes do primeiro
+        let cycle: Vec<usize> = cyclic.get_cycle(4).unwrap().unwrap();
+        assert_eq!(cycle.iter().copied().sorted().collect::<Vec<usize>>(), vec![1, 2, 3]);
+        assert!(cycle.iter().circular_tuple_windows().all(|(&a, &b)| cyclic.get_pairs().contains(&(a, b))));
+    }
+
+    #[test]
+    fn violations_known_answer() {
+        let precedences: Precedences = Precedences::new(vec![(1, 2), (2, 3), (1, 4)]);
+
+        assert_eq!(precedences.get_violations(&[0, 2, 1, 3, 4], 0), vec![(1, 2)]);
+        // A ordem é contada a partir do deposito, esteja ele onde estiver na rota
+        assert_eq!(precedences.get_violations(&[3, 4, 0, 1, 2], 0), Vec::<(usize, usize)>::new());
+        assert_eq!(precedences.get_violations(&[3, 4, 0, 1, 2], 2), vec![(1, 2), (1, 4)]);
+        // Vertices fora da rota violam os seus pares
+        assert_eq!(precedences.get_violations(&[0, 1, 2, 3], 0), vec![(1, 4)]);
+        assert!(precedences.is_satisfied(&[0, 1, 4, 2, 3], 0));
+    }
+
+    #[test]
+    fn infeasible_instances() {
+        let t: Graph<usize> = random_graph(4, true, &mut 0);
+
+        assert!(t.check_precedences(0, &Precedences::new(vec![(1, 2)])).is_ok());
+        assert!(t.check_precedences(4, &Precedences::new(vec![])).is_err());
+        assert!(t.check_precedences(0, &Precedences::new(vec![(1, 4)])).is_err());
+        assert!(t.check_precedences(0, &Precedences::new(vec![(1, 0)])).is_err());
+        assert!(t.check_precedences(0, &Precedences::new(vec![(1, 2), (2, 1)])).is_err());
+        assert!(t.tsp_precedence(0, &Precedences::new(vec![(1, 2), (2, 3), (3, 1)]), false).is_err());
+    }
+
+    #[test]
+    fn precedence_known_answer() {
+        // A rota mais barata sem restricoes é 0 -> 3 -> 2 -> 1, mas as precedencias so permitem 0 -> 1 -> 2 -> 3
+        let t: Graph<usize> = Graph::from((0..4).collect(), vec![
+            vec![0, 9, 9, 1],
+            vec![1, 0, 9, 9],
+            vec![9, 1, 0, 9],
+            vec![9, 9, 1, 0]
+        ]);
+
+        assert_eq!(t.solve(crate::solver::Solver::HeldKarp, false).unwrap().1, 4);
+        assert_eq!(t.tsp_precedence(0, &Precedences::new(vec![(1, 2), (2, 3)]), false), Ok((vec![0, 1, 2, 3], 36)));
+    }
+
+    #[test]
+    fn routes_are_feasible() {
+        let mut seed: u64 = 52;
+
+        for n in [3, 5, 7] {
+            for symmetric in [true, false] {
+                let t: Graph<usize> = random_graph(n, symmetric, &mut seed);
+                let depot_idx: usize = n - 1;
+                // Pares (a, b) com a < b nunca formam ciclo
+                let pairs: Vec<(usize, usize)> = (0..n)
+                    .flat_map(|a| (a + 1..n - 1).map(move |b| (a, b)))
+                    .filter(|_| next_random(&mut seed).is_multiple_of(3))
+                    .collect();
+                let precedences: Precedences = Precedences::new(pairs);
+                let optimum: u64 = (0..n - 1).permutations(n - 1)
+                    .map(|p| [depot_idx].into_iter().chain(p).collect::<Vec<usize>>())
+                    .filter(|route| precedences.is_satisfied(route, depot_idx))
+                    .map(|route| t.get_route_cost(&route).unwrap())
+                    .min()
+                    .unwrap();
+                let (route, cost) = t.tsp_precedence(depot_idx, &precedences, false).unwrap();
+
+                assert_eq!(route[0], depot_idx);
+                assert!(route.iter().copied().sorted().eq(0..n));
+                assert!(precedences.is_satisfied(&route, depot_idx));
+                assert_eq!(t.get_route_cost(&route), Ok(cost));
+                assert!(cost >= optimum);
+            }
+        }
+    }
+
+    #[test]
+    fn small_graphs() {
+        let single: Graph<usize> = Graph::from(vec![0], vec![vec![0]]);
+        let pair: Graph<usize> = Graph::from(vec![0, 1], vec![vec![0, 3], vec![7, 0]]);
+
+        assert_eq!(single.tsp_precedence(0, &Precedences::new(vec![]), false), Ok((vec![0], 0)));
+        assert_eq!(pair.tsp_precedence(1, &Precedences::new(vec![(1, 0)]), false), Ok((vec![1, 0], 10)));
+        assert!(pair.tsp_precedence(1, &Precedences::new(vec![(0, 1)]), false).is_err());
+    }
+}
@@ -0,0 +1,423 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::time::Instant;
+use crate::graph::Graph;
+use crate::solver::Solver;
+
+// Rota que nao precisa visitar todas as cidades: sai do deposito (route[0]) e volta a ele. `collected` soma
+// o valor das cidades visitadas (premio) e `skipped_total` o das cidades que ficaram de fora (penalidade).
+pub struct PrizeRoute {
+    route: Vec<usize>,
+    length: u64,
+    collected: u64,
+    skipped: Vec<usize>,
+    skipped_total: u64
+}
+
+impl PrizeRoute {
+    pub fn get_route(&self) -> &Vec<usize> {
+        &self.route
+    }
+
+    pub fn get_length(&self) -> u64 {
+        self.length
+    }
+
+    pub fn get_collected(&self) -> u64 {
+        self.collected
+    }
+
+    pub fn get_skipped(&self) -> &Vec<usize> {
+        &self.skipped
+    }
+
+    pub fn get_skipped_total(&self) -> u64 {
+        self.skipped_total
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Verificacoes comuns das variantes com premio
+    fn check_prize_instance(&self, depot_idx: usize, values: &[u64]) -> Result<(), &'static str> {
+        self.check_complete()?;
+
+        if self.num_vertices() <= depot_idx {
+            return Err("Index out of range!");
+        }
+
+        if values.len() != self.num_vertices() {
+            return Err("There must be one prize per vertex!");
+        }
+
+        Ok(())
+    }
+
+    // Menor aumento de custo para inserir v na rota fechada e a posicao onde ele entra (nunca antes do deposito).
+    // Pode ser negativo em matrizes que nao respeitam a desigualdade triangular.
+    fn cheapest_insertion(&self, route: &[usize], v: usize) -> (i64, usize) {
+        let w = |i: usize, j: usize| -> i64 { self.weight(i, j) as i64 };
+
+        (1..=route.len())
+            .map(|p| {
+                let (a, b) = (route[p - 1], route[p % route.len()]);
+                (w(a, v) + w(v, b) - w(a, b), p)
+            })
+            .min()
+            .unwrap()
+    }
+
+    // Quanto a rota encurta tirando a cidade da posicao i (negativo se ela ficar mais longa)
+    fn removal_gain(&self, route: &[usize], i: usize) -> i64 {
+        let w = |i: usize, j: usize| -> i64 { self.weight(i, j) as i64 };
+        let (a, v, b) = (route[i - 1], route[i], route[(i + 1) % route.len()]);
+
+        w(a, v) + w(v, b) - w(a, b)
+    }
+
+    // Melhora a ordem da rota com 3-opt sem inversao e or-opt, mantendo o deposito na posicao 0
+    fn improve_prize_route(&self, route: &mut Vec<usize>) {
+        let depot_idx: usize = route[0];
+
+        while self.improve_segment_insertion(route) | self.improve_or_opt(route) {}
+
+        let depot_position: usize = route.iter().position(|&v| v == depot_idx).unwrap();
+        route.rotate_left(depot_position);
+    }
+
+    // Comprimento da rota depois de inserir v na posicao
+    fn insertion_length(&self, route: &[usize], v: usize, position: usize) -> u64 {
+        let mut candidate: Vec<usize> = route.to_vec();
+        candidate.insert(position, v);
+
+        self.get_route_cost(&candidate).unwrap()
+    }
+
+    fn build_prize_route(&self, route: Vec<usize>, values: &[u64]) -> Result<PrizeRoute, &'static str> {
+        let mut visited: Vec<bool> = vec![false; self.num_vertices()];
+
+        for &v in route.iter() {
+            visited[v] = true;
+        }
+
+        let skipped: Vec<usize> = (0..self.num_vertices()).filter(|&v| !visited[v]).collect();
+
+        Ok(PrizeRoute {
+            length: self.get_route_cost(&route)?,
+            collected: route.iter().map(|&v| values[v]).sum(),
+            skipped_total: skipped.iter().map(|&v| values[v]).sum(),
+            route,
+            skipped
+        })
+    }
+
+    // Busca local do PCTSP a partir de uma rota com o deposito na posicao 0. Cada movimento diminui o
+    // comprimento mais as penalidades: add, drop, troca de uma cidade da rota por uma de fora e reordenacao.
+    fn pctsp_local_search(&self, route: &mut Vec<usize>, penalties: &[u64]) -> usize {
+        let n: usize = self.num_vertices();
+        let mut in_route: Vec<bool> = vec![false; n];
+        let mut counter: usize = 0;
+
+        for &v in route.iter() {
+            in_route[v] = true;
+        }
+
+        self.improve_prize_route(route);
+
+        loop {
+            counter += 1;
+
+            // Add: a cidade com a maior economia (penalidade - custo de insercao)
+            let best_add: Option<(i64, usize, usize)> = (0..n)
+                .filter(|&v| !in_route[v])
+                .map(|v| {
+                    let (cost, position) = self.cheapest_insertion(route, v);
+                    (penalties[v] as i64 - cost, v, position)
+                })
+                .filter(|&(gain, _, _)| gain > 0)
+                .max();
+
+            if let Some((_, v, position)) = best_add {
+                route.insert(position, v);
+                in_route[v] = true;
+                self.improve_prize_route(route);
+                continue;
+            }
+
+            // Drop: a cidade cuja retirada encurta a rota mais do que a penalidade que passa a ser paga
+            let best_drop: Option<(i64, usize)> = (1..route.len())
+                .map(|i| (self.removal_gain(route, i) - penalties[route[i]] as i64, i))
+                .filter(|&(gain, _)| gain > 0)
+                .max();
+
+            if let Some((_, i)) = best_drop {
+                in_route[route.remove(i)] = false;
+                self.improve_prize_route(route);
+                continue;
+            }
+
+            // Drop + add: tira a cidade da posicao i e insere v no melhor lugar
+            let mut best_swap: Option<(i64, usize, usize, usize)> = None;
+
+            for i in 1..route.len() {
+                let mut without: Vec<usize> = route.clone();
+                let removed: usize = without.remove(i);
+                let removal: i64 = self.removal_gain(route, i) - penalties[removed] as i64;
+
+                for v in (0..n).filter(|&v| !in_route[v]) {
+                    let (cost, position) = self.cheapest_insertion(&without, v);
+                    let gain: i64 = removal + penalties[v] as i64 - cost;
+
+                    if gain > 0 && best_swap.is_none_or(|(actual, _, _, _)| gain > actual) {
+                        best_swap = Some((gain, i, v, position));
+                    }
+                }
+            }
+
+            if let Some((_, i, v, position)) = best_swap {
+                in_route[route.remove(i)] = false;
+                route.insert(position, v);
+                in_route[v] = true;
+                self.improve_prize_route(route);
+                continue;
+            }
+
+            return counter;
+        }
+    }
+
+    // TSP com coleta de premios (versao com penalidades): cada cidade fora da rota custa `penalties[v]`, e o
+    // objetivo é o comprimento da rota mais as penalidades das cidades puladas. A busca local alterna
+    // adicionar uma cidade (quando inserir custa menos que a penalidade), tirar uma cidade (quando a rota
+    // encurta mais que a penalidade), trocar uma cidade por outra e reordenar a rota. Parte tanto do deposito
+    // sozinho quanto da rota do 3-opt com todas as cidades e fica com o melhor resultado. O deposito sempre
+    // fica na rota.
+    pub fn pctsp_approx(&self, depot_idx: usize, penalties: &[u64], log: bool) -> Result<PrizeRoute, &'static str> {
+        self.check_prize_instance(depot_idx, penalties)?;
+
+        let start_time = Instant::now();
+
+        let (mut full_route, _) = self.solve(Solver::ThreeOpt, false)?;
+        let depot_position: usize = full_route.iter().position(|&v| v == depot_idx).unwrap();
+        full_route.rotate_left(depot_position);
+
+        let mut counter: usize = 0;
+        let mut best: Option<PrizeRoute> = None;
+
+        for mut route in [vec![depot_idx], full_route] {
+            counter += self.pctsp_local_search(&mut route, penalties);
+
+            let candidate: PrizeRoute = self.build_prize_route(route, penalties)?;
+
+            if best.as_ref().is_none_or(|b| candidate.length + candidate.skipped_total < b.length + b.skipped_total) {
+                best = Some(candidate);
+            }
+        }
+
+        let prize_route: PrizeRoute = best.unwrap();
+
+        let end_time = Instant::now();
+
+        if log {
+            println!("Time elapsed: {} - Total iterations: {} - Route length: {} - Penalties: {} - Objective: {} - Route found: {}", end_time.duration_since(start_time).as_micros(), counter, prize_route.length, prize_route.skipped_total, prize_route.length + prize_route.skipped_total, prize_route.route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
+        }
+
+        Ok(prize_route)
+    }
+
+    // Problema de orientacao (orienteering): maximiza o premio coletado com uma rota de comprimento no maximo
+    // `budget`. Adiciona cidades pela razao premio / custo de insercao enquanto couberem no orcamento; depois
+    // reordena a rota para liberar orcamento, tenta adicionar mais cidades e troca uma cidade da rota por uma
+    // de fora com premio maior (drop + add), ate nao haver melhora.
+    pub fn orienteering_approx(&self, depot_idx: usize, prizes: &[u64], budget: u64, log: bool) -> Result<PrizeRoute, &'static str> {
+        self.check_prize_instance(depot_idx, prizes)?;
+
+        let start_time = Instant::now();
+
+        let n: usize = self.num_vertices();
+        let mut route: Vec<usize> = vec![depot_idx];
+        let mut in_route: Vec<bool> = (0..n).map(|v| v == depot_idx).collect();
+        let mut counter: usize = 0;
+
+        loop {
+            counter += 1;
+
+            // Add: a cidade que cabe no orcamento com a maior razao premio / custo de insercao
+            let best_add: Option<(usize, usize, u64)> = (0..n)
+                .filter(|&v| !in_route[v] && prizes[v] > 0)
+                .map(|v| {
+                    let (cost, position) = self.cheapest_insertion(&route, v);
+                    (v, position, cost.max(0) as u64)
+                })
+                .filter(|&(v, position, _)| self.insertion_length(&route, v, position) <= budget)
+                .max_by(|&(u, _, cost_u), &(v, _, cost_v)| (prizes[u] as u128 * (cost_v as u128 + 1)).cmp(&(prizes[v] as u128 * (cost_u as u128 + 1))));
+
+            if let Some((v, position, _)) = best_add {
+                route.insert(position, v);
+                in_route[v] = true;
+                self.improve_prize_route(&mut route);
+                continue;
+            }
+
+            // Drop + add: tira a cidade da posicao i e coloca uma de fora com premio maior, se couber
+            let mut swapped: bool = false;
+
+            'swap: for i in 1..route.len() {
+                let mut without: Vec<usize> = route.clone();
+                let removed: usize = without.remove(i);
+
+                for v in (0..n).filter(|&v| !in_route[v] && prizes[v] > prizes[removed]) {
+                    let (_, position) = self.cheapest_insertion(&without, v);
+
+                    if self.insertion_length(&without, v, position) <= budget {
+                        without.insert(position, v);
+                        in_route[removed] = false;
+                        in_route[v] = true;
+                        route = without;
+                        self.improve_prize_route(&mut route);
+                        swapped = true;
+                        break 'swap;
+                    }
+                }
+            }
+
+            if !swapped {
+                break;
+            }
+        }
+
+        let prize_route: PrizeRoute = self.build_prize_route(route, prizes)?;
+
+        let end_time = Instant::now();
+
+        if log {
+            println!("Time elapsed: {} - Total iterations: {} - Prize collected: {} - Route length: {} (budget {}) - Route found: {}", end_time.duration_since(start_time).as_micros(), counter, prize_route.collected, prize_route.length, budget, prize_route.route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
+        }
+
+        Ok(prize_route)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{next_random, random_graph};
+    use itertools::Itertools;
+
+    // Menor rota fechada por cada subconjunto de cidades que contem o deposito: (cidades, comprimento)
+    fn all_subset_routes(t: &Graph<usize>, depot_idx: usize) -> Vec<(Vec<usize>, u64)> {
+        let others: Vec<usize> = (0..t.num_vertices()).filter(|&v| v != depot_idx).collect();
+
+        others.iter().copied().powerset()
+            .map(|subset| {
+                let length: u64 = subset.iter().copied().permutations(subset.len())
+                    .map(|p| t.get_route_cost(&[depot_idx].into_iter().chain(p).collect::<Vec<usize>>()).unwrap())
+                    .min()
+                    .unwrap();
+
+                ([depot_idx].into_iter().chain(subset).collect(), length)
+            })
+            .collect()
+    }
+
+    // Confere que a rota comeca no deposito, nao repete cidades e que os totais batem
+    fn is_consistent(t: &Graph<usize>, depot_idx: usize, values: &[u64], p: &PrizeRoute) -> bool {
+        let visited: Vec<usize> = p.get_route().iter().copied().sorted().collect();
+
+        p.get_route()[0] == depot_idx
+            && visited.iter().copied().dedup().count() == visited.len()
+            && visited.iter().chain(p.get_skipped().iter()).copied().sorted().eq(0..t.num_vertices())
+            && t.get_route_cost(p.get_route()) == Ok(p.get_length())
+            && p.get_collected() == visited.iter().map(|&v| values[v]).sum::<u64>()
+            && p.get_skipped_total() == p.get_skipped().iter().map(|&v| values[v]).sum::<u64>()
+    }
+
+    #[test]
+    fn pctsp_is_never_better_than_brute_force() {
+        let mut seed: u64 = 49;
+
+        for n in [2, 4, 6, 7] {
+            for symmetric in [true, false] {
+                let t: Graph<usize> = random_graph(n, symmetric, &mut seed);
+                let penalties: Vec<u64> = (0..n).map(|_| next_random(&mut seed) % 40).collect();
+                let total: u64 = penalties.iter().sum();
+                let optimum: u64 = all_subset_routes(&t, 0).into_iter()
+                    .map(|(cities, length)| length + total - cities.iter().map(|&v| penalties[v]).sum::<u64>())
+                    .min()
+                    .unwrap();
+                let result: PrizeRoute = t.pctsp_approx(0, &penalties, false).unwrap();
+
+                assert!(is_consistent(&t, 0, &penalties, &result));
+                assert!(result.get_length() + result.get_skipped_total() >= optimum);
+            }
+        }
+    }
+
+    #[test]
+    fn orienteering_is_never_better_than_brute_force() {
+        let mut seed: u64 = 50;
+
+        for n in [2, 4, 6, 7] {
+            for symmetric in [true, false] {
+                let t: Graph<usize> = random_graph(n, symmetric, &mut seed);
+                let prizes: Vec<u64> = (0..n).map(|_| next_random(&mut seed) % 40).collect();
+                let routes: Vec<(Vec<usize>, u64)> = all_subset_routes(&t, 0);
+
+                for budget in [0, 30, 60, 1000] {
+                    let optimum: u64 = routes.iter()
+                        .filter(|(_, length)| *length <= budget)
+                        .map(|(cities, _)| cities.iter().map(|&v| prizes[v]).sum::<u64>())
+                        .max()
+                        .unwrap();
+                    let result: PrizeRoute = t.orienteering_approx(0, &prizes, budget, false).unwrap();
+
+                    assert!(is_consistent(&t, 0, &prizes, &result));
+                    assert!(result.get_length() <= budget);
+                    assert!(result.get_collected() <= optimum);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn prize_known_answer() {
+        // Cidades 1 e 2 perto do deposito, 3 longe
+        let t: Graph<usize> = Graph::from((0..4).collect(), vec![
+            vec![0, 1, 2, 20],
+            vec![1, 0, 1, 20],
+            vec![2, 1, 0, 20],
+            vec![20, 20, 20, 0]
+        ]);
+
+        // Pular a cidade 3 custa 5, menos que os 38 a mais para visita-la
+        let pctsp: PrizeRoute = t.pctsp_approx(0, &[0, 10, 10, 5], false).unwrap();
+        assert_eq!((pctsp.get_length(), pctsp.get_skipped().clone(), pctsp.get_skipped_total()), (4, vec![3], 5));
+
+        // Penalidade zero: fica so o deposito
+        let pctsp: PrizeRoute = t.pctsp_approx(0, &[0, 0, 0, 0], false).unwrap();
+        assert_eq!((pctsp.get_route().clone(), pctsp.get_length()), (vec![0], 0));
+
+        // Orcamento exato para a volta completa 0 -> 1 -> 2 -> 3 -> 0
+        let orienteering: PrizeRoute = t.orienteering_approx(0, &[0, 1, 1, 10], 42, false).unwrap();
+        assert_eq!((orienteering.get_length(), orienteering.get_collected(), orienteering.get_skipped().clone()), (42, 12, vec![]));
+
+        let orienteering: PrizeRoute = t.orienteering_approx(0, &[0, 1, 1, 10], 4, false).unwrap();
+        assert_eq!((orienteering.get_length(), orienteering.get_collected()), (4, 2));
+    }
+
+    #[test]
+    fn small_and_invalid_instances() {
+        let single: Graph<usize> = Graph::from(vec![0], vec![vec![0]]);
+        let pair: Graph<usize> = Graph::from(vec![0, 1], vec![vec![0, 3], vec![7, 0]]);
+
+        assert_eq!(single.pctsp_approx(0, &[5], false).unwrap().get_route(), &vec![0]);
+        assert_eq!(single.orienteering_approx(0, &[5], 0, false).unwrap().get_collected(), 5);
+
+        assert_eq!(pair.pctsp_approx(1, &[11, 0], false).unwrap().get_route(), &vec![1, 0]);
+        assert_eq!(pair.pctsp_approx(1, &[9, 0], false).unwrap().get_skipped(), &vec![0]);
+        assert_eq!(pair.orienteering_approx(0, &[0, 1], 9, false).unwrap().get_route(), &vec![0]);
+        assert_eq!(pair.orienteering_approx(0, &[0, 1], 10, false).unwrap().get_route(), &vec![0, 1]);
+
+        assert!(pair.pctsp_approx(2, &[0, 1], false).is_err());
+        assert!(pair.orienteering_approx(0, &[0], 10, false).is_err());
+    }
+}
@@ -0,0 +1,618 @@
+use std::collections::BinaryHeap;
+use std::cmp::Reverse;
+use std::fmt::Display;
+use std::hash::Hash;
+use crate::coordinates::{haversine_distance, Coordinates, Metric};
+use crate::graph::Graph;
+use crate::signed_graph::SignedGraph;
+
+// Arvore de caminhos minimos a partir de uma origem: distancia e predecessor de cada vertice.
+// Vertices inalcancaveis ficam com distancia None.
+pub struct ShortestPaths<W: Copy> {
+    source: usize,
+    distances: Vec<Option<W>>,
+    predecessors: Vec<Option<usize>>
+}
+
+impl<W: Copy> ShortestPaths<W> {
+    pub fn new(source: usize, distances: Vec<Option<W>>, predecessors: Vec<Option<usize>>) -> Self {
+        ShortestPaths {
+            source,
+            distances,
+            predecessors
+        }
+    }
+
+    pub fn get_source(&self) -> usize {
+        self.source
+    }
+
+    pub fn get_distances(&self) -> &Vec<Option<W>> {
+        &self.distances
+    }
+
+    pub fn get_predecessors(&self) -> &Vec<Option<usize>> {
+        &self.predecessors
+    }
+
+    pub fn distance_to(&self, vertex_idx: usize) -> Option<W> {
+        self.distances.get(vertex_idx).copied().flatten()
+    }
+
+    // Caminho da origem ate o vertice (inclusive) e o seu custo, None se o vertice é inalcancavel
+    pub fn path_to(&self, vertex_idx: usize) -> Option<(Vec<usize>, W)> {
+        let cost: W = self.distance_to(vertex_idx)?;
+        let mut path: Vec<usize> = vec![vertex_idx];
+        let mut actual_vertex: usize = vertex_idx;
+
+        while let Some(previous) = self.predecessors[actual_vertex] {
+            actual_vertex = previous;
+            path.push(actual_vertex);
+        }
+
+        path.reverse();
+
+        Some((path, cost))
+    }
+}
+
+// Estimativa do custo restante de um vertice ate o destino usada pelo A*. Para o caminho encontrado
+// ser o minimo a estimativa precisa ser admissivel (nunca maior que o custo real). Nao precisa ser
+// consistente: o A* reabre vertices quando acha um caminho mais barato ate eles.
+pub trait Heuristic {
+    fn estimate(&self, vertex_idx: usize, dst_idx: usize) -> u64;
+}
+
+// Qualquer closure |vertice, destino| -> estimativa serve como heuristica
+impl<F: Fn(usize, usize) -> u64> Heuristic for F {
+    fn estimate(&self, vertex_idx: usize, dst_idx: usize) -> u64 {
+        self(vertex_idx, dst_idx)
+    }
+}
+
+// Folga para pesos arredondados para o inteiro mais proximo: cada aresta pode custar ate meio a menos que a
+// distancia real, e um caminho simples tem no maximo n - 1 arestas. Pesos arredondados para cima nao
+// precisam de folga.
+fn get_rounding_slack(coordinates: &Coordinates) -> f64 {
+    match coordinates.get_metric() {
+        Metric::EuclideanCeil => 0.0,
+        _ => 0.5 * coordinates.len().saturating_sub(1) as f64
+    }
+}
+
+// Distancia em linha reta menos a folga de arredondamento, arredondada para baixo. Exata para CEIL_2D; no
+// EUC_2D a folga deixa a estimativa admissivel, mas fraca em grafos grandes.
+pub struct EuclideanHeuristic<'a> {
+    coordinates: &'a Coordinates,
+    slack: f64
+}
+
+impl<'a> EuclideanHeuristic<'a> {
+    pub fn new(coordinates: &'a Coordinates) -> Self {
+        EuclideanHeuristic {
+            coordinates,
+            slack: get_rounding_slack(coordinates)
+        }
+    }
+}
+
+impl Heuristic for EuclideanHeuristic<'_> {
+    fn estimate(&self, vertex_idx: usize, dst_idx: usize) -> u64 {
+        let (x1, y1) = self.coordinates.get_point(vertex_idx).unwrap();
+        let (x2, y2) = self.coordinates.get_point(dst_idx).unwrap();
+
+        (((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt() - self.slack).max(0.0).floor() as u64
+    }
+}
+
+// Distancia de grande circulo em km para pontos (latitude, longitude), com a mesma folga de arredondamento
+pub struct HaversineHeuristic<'a> {
+    coordinates: &'a Coordinates,
+    slack: f64
+}
+
+impl<'a> HaversineHeuristic<'a> {
+    pub fn new(coordinates: &'a Coordinates) -> Self {
+        HaversineHeuristic {
+            coordinates,
+            slack: get_rounding_slack(coordinates)
+        }
+    }
+}
+
+impl Heuristic for HaversineHeuristic<'_> {
+    fn estimate(&self, vertex_idx: usize, dst_idx: usize) -> u64 {
+        let a: (f64, f64) = self.coordinates.get_point(vertex_idx).unwrap();
+        let b: (f64, f64) = self.coordinates.get_point(dst_idx).unwrap();
+
+        (haversine_distance(a, b) - self.slack).max(0.0).floor() as u64
+    }
+}
+
+// Fecho de caminhos minimos (todos os pares) calculado pelo Floyd-Warshall. `next_hop[i][j]` é o vertice
+// seguinte a i no caminho minimo de i ate j, o que permite expandir cada trecho de uma rota no caminho real.
+pub struct MetricClosure {
+    distances: Vec<Vec<Option<u64>>>,
+    next_hop: Vec<Vec<Option<usize>>>
+}
+
+impl MetricClosure {
+    pub fn get_distances(&self) -> &Vec<Vec<Option<u64>>> {
+        &self.distances
+    }
+
+    pub fn get_next_hop(&self) -> &Vec<Vec<Option<usize>>> {
+        &self.next_hop
+    }
+
+    pub fn distance(&self, src_idx: usize, dst_idx: usize) -> Option<u64> {
+        *self.distances.get(src_idx)?.get(dst_idx)?
+    }
+
+    // Caminho real de src ate dst (inclusive), None se dst é inalcancavel
+    pub fn path(&self, src_idx: usize, dst_idx: usize) -> Option<Vec<usize>> {
+        self.distance(src_idx, dst_idx)?;
+
+        let mut path: Vec<usize> = vec![src_idx];
+        let mut actual_vertex: usize = src_idx;
+
+        while actual_vertex != dst_idx {
+            actual_vertex = self.next_hop[actual_vertex][dst_idx]?;
+            path.push(actual_vertex);
+        }
+
+        Some(path)
+    }
+
+    // Grafo com os vertices de `graph` e o custo do caminho minimo entre cada par como peso da aresta.
+    // Pares inalcancaveis ficam sem aresta.
+    pub fn to_graph<V: Eq + Hash + Display + Clone>(&self, graph: &Graph<V>) -> Graph<V> {
+        let adjacency_matrix: Vec<Vec<u64>> = self.distances.iter()
+            .map(|row| row.iter().map(|d| d.unwrap_or(0)).collect())
+            .collect();
+
+        Graph::from(graph.vertices().map(|(_, v)| v.clone()).collect(), adjacency_matrix)
+    }
+
+    // Expande uma rota (ciclo) calculada sobre o fecho trocando cada trecho, inclusive o de volta ao inicio,
+    // pelo caminho real. O resultado é um passeio fechado que pode repetir vertices, com o mesmo custo da rota.
+    pub fn expand_route(&self, route: &[usize]) -> Result<Vec<usize>, &'static str> {
+        // Com um vertice so nao ha trecho a expandir
+        if route.len() <= 1 {
+            return Ok(route.to_vec());
+        }
+
+        let mut walk: Vec<usize> = Vec::with_capacity(route.len());
+
+        for i in 0..route.len() {
+            let leg: Vec<usize> = match self.path(route[i], route[(i + 1) % route.len()]) {
+                Some(p) => p,
+                None => return Err("Route uses an unreachable pair!")
+            };
+
+            // O ultimo vertice de cada trecho é o primeiro do proximo
+            walk.extend_from_slice(&leg[..leg.len() - 1]);
+        }
+
+        Ok(walk)
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Algoritmo de Floyd-Warshall: https://en.wikipedia.org/wiki/Floyd%E2%80%93Warshall_algorithm
+    // O(n³). Resolver o TSP sobre o fecho deixa a instancia metrica (garantias do Christofides e da
+    // arvore dupla), e `MetricClosure::expand_route` traduz a rota de volta para o grafo original.
+    pub fn get_metric_closure(&self) -> MetricClosure {
+        let n: usize = self.num_vertices();
+        let mut distances: Vec<Vec<Option<u64>>> = vec![vec![None; n]; n];
+        let mut next_hop: Vec<Vec<Option<usize>>> = vec![vec![None; n]; n];
+
+        for (i, row) in distances.iter_mut().enumerate() {
+            row[i] = Some(0);
+            next_hop[i][i] = Some(i);
+        }
+
+        for edge in self.edges() {
+            let (src, dst) = (*edge.get_src(), *edge.get_dst());
+
+            if src != dst && distances[src][dst].is_none_or(|d| edge.get_weight() < d) {
+                distances[src][dst] = Some(edge.get_weight());
+                next_hop[src][dst] = Some(dst);
+            }
+        }
+
+        for k in 0..n {
+            for i in 0..n {
+                let cost_ik: u64 = match distances[i][k] {
+                    Some(d) => d,
+                    None => continue
+                };
+
+                for j in 0..n {
+                    if let Some(cost_kj) = distances[k][j] {
+                        if distances[i][j].is_none_or(|d| cost_ik + cost_kj < d) {
+                            distances[i][j] = Some(cost_ik + cost_kj);
+                            next_hop[i][j] = next_hop[i][k];
+                        }
+                    }
+                }
+            }
+        }
+
+        MetricClosure {
+            distances,
+            next_hop
+        }
+    }
+
+    // Algoritmo de Dijkstra a partir de `src_idx`. Com `target` a busca para assim que o destino é fechado,
+    // e só as distancias dos vertices ja fechados sao finais.
+    fn dijkstra(&self, src_idx: usize, target: Option<usize>) -> Result<ShortestPaths<u64>, &'static str> {
+        let n: usize = self.num_vertices();
+        let mut previous_vertex: Vec<Option<usize>> = vec![None; n];
+        let mut path_cost: Vec<Option<u64>> = vec![None; n];
+        let mut is_closed: Vec<bool> = vec![false; n];
+        let mut vert_to_visit: BinaryHeap<Reverse<(u64, usize)>> = BinaryHeap::new();
+
+        // custo do no inicial é 0
+        path_cost[src_idx] = Some(0);
+        // insere o no inicial na heap
+        vert_to_visit.push(Reverse((0, src_idx)));
+
+        // remove o primeiro nó da heap
+        while let Some(Reverse((cost, vertex_idx))) = vert_to_visit.pop() {
+            // verifica se esse no ja nao esta fechado
+            if is_closed[vertex_idx] {
+                continue;
+            }
+
+            // fecha o no
+            is_closed[vertex_idx] = true;
+
+            if target == Some(vertex_idx) {
+                break;
+            }
+
+            // verifica os adjacentes desse no, atualiza seus pesos e adiciona na heap
+            for (idx, weight) in self.neighbors(vertex_idx)? {
+                if is_closed[idx] {
+                    continue;
+                }
+
+                let total_cost: u64 = cost + weight;
+
+                if path_cost[idx].is_none_or(|c| c > total_cost) {
+                    path_cost[idx] = Some(total_cost);
+                    previous_vertex[idx] = Some(vertex_idx);
+                    vert_to_visit.push(Reverse((total_cost, idx)));
+                }
+            }
+        }
+
+        Ok(ShortestPaths::new(src_idx, path_cost, previous_vertex))
+    }
+
+    // Arvore de caminhos minimos de `src_idx` para todos os vertices
+    pub fn get_dijkstra(&self, src_idx: usize) -> Result<ShortestPaths<u64>, &'static str> {
+        if self.num_vertices() <= src_idx {
+            return Err("Index out of range!");
+        }
+
+        self.dijkstra(src_idx, None)
+    }
+
+    // Caminho minimo e custo de src ate dst, parando a busca assim que dst é alcancado.
+    // None quando dst é inalcancavel.
+    pub fn get_dijkstra_path(&self, src_idx: usize, dst_idx: usize) -> Result<Option<(Vec<usize>, u64)>, &'static str> {
+        if self.num_vertices() <= src_idx || self.num_vertices() <= dst_idx {
+            return Err("Index out of range!");
+        }
+
+        Ok(self.dijkstra(src_idx, Some(dst_idx))?.path_to(dst_idx))
+    }
+
+    // Algoritmo A*: https://en.wikipedia.org/wiki/A*_search_algorithm
+    // Dijkstra guiado pela heuristica: o vertice aberto é o de menor custo + estimativa ate o destino, o que
+    // reduz muito os vertices explorados quando a estimativa é boa. Um vertice ja fechado volta para a fila
+    // se aparecer um caminho mais barato ate ele, o que mantem o resultado minimo com heuristicas admissiveis
+    // mas inconsistentes. Retorna o caminho e o custo, ou None se o destino é inalcancavel.
+    pub fn astar<H: Heuristic + ?Sized>(&self, src_idx: usize, dst_idx: usize, heuristic: &H) -> Result<Option<(Vec<usize>, u64)>, &'static str> {
+        if self.num_vertices() <= src_idx || self.num_vertices() <= dst_idx {
+            return Err("Index out of range!");
+        }
+
+        let n: usize = self.num_vertices();
+        let mut path_cost: Vec<Option<u64>> = vec![None; n];
+        let mut previous_vertex: Vec<Option<usize>> = vec![None; n];
+        // (custo + estimativa, custo, vertice)
+        let mut vert_to_visit: BinaryHeap<Reverse<(u64, u64, usize)>> = BinaryHeap::new();
+
+        path_cost[src_idx] = Some(0);
+        vert_to_visit.push(Reverse((heuristic.estimate(src_idx, dst_idx), 0, src_idx)));
+
+        while let Some(Reverse((_, cost, vertex_idx))) = vert_to_visit.pop() {
+            if vertex_idx == dst_idx {
+                let paths: ShortestPaths<u64> = ShortestPaths::new(src_idx, path_cost, previous_vertex);
+
+                return Ok(paths.path_to(dst_idx));
+            }
+
+            // Entrada antiga: o vertice ja foi alcancado por um caminho mais barato
+            if path_cost[vertex_idx].is_some_and(|c| cost > c) {
+                continue;
+            }
+
+            for (idx, weight) in self.neighbors(vertex_idx)? {
+                let total_cost: u64 = cost + weight;
+
+                if path_cost[idx].is_none_or(|c| total_cost < c) {
+                    path_cost[idx] = Some(total_cost);
+                    previous_vertex[idx] = Some(vertex_idx);
+                    vert_to_visit.push(Reverse((total_cost + heuristic.estimate(idx, dst_idx), total_cost, idx)));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Heuristica embutida para grafos baseados em coordenadas: linha reta para as metricas euclidianas e
+    // haversine para latitude/longitude. None para as demais metricas ou grafos sem coordenadas.
+    pub fn get_coordinate_heuristic(&self) -> Option<Box<dyn Heuristic + '_>> {
+        let coordinates: &Coordinates = self.get_coordinates()?;
+
+        match coordinates.get_metric() {
+            Metric::Euclidean | Metric::EuclideanCeil => Some(Box::new(EuclideanHeuristic::new(coordinates))),
+            Metric::Haversine => Some(Box::new(HaversineHeuristic::new(coordinates))),
+            _ => None
+        }
+    }
+
+    // Verifica a desigualdade triangular w(i, j) <= w(i, k) + w(k, j) para todas as triplas. O(n³)
+    pub fn is_metric(&self) -> bool {
+        let n: usize = self.num_vertices();
+
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    if i == j || i == k || j == k {
+                        continue;
+                    }
+
+                    let (w_ij, w_ik, w_kj) = (self.get_edge_weight(i, j).unwrap(), self.get_edge_weight(i, k).unwrap(), self.get_edge_weight(k, j).unwrap());
+
+                    if w_ij > w_ik + w_kj {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone> SignedGraph<V> {
+    // Algoritmo de Bellman-Ford: https://en.wikipedia.org/wiki/Bellman%E2%80%93Ford_algorithm
+    // Aceita pesos negativos. Se existir um ciclo negativo alcancavel a partir da origem as distancias
+    // nao estao definidas e é retornado erro (ver `get_negative_cycle` para encontrar o ciclo).
+    pub fn bellman_ford(&self, src_idx: usize) -> Result<ShortestPaths<i64>, &'static str> {
+        if self.num_vertices() <= src_idx {
+            return Err("Index out of range!");
+        }
+
+        let n: usize = self.num_vertices();
+        let mut distances: Vec<Option<i64>> = vec![None; n];
+        let mut predecessors: Vec<Option<usize>> = vec![None; n];
+
+        distances[src_idx] = Some(0);
+
+        // Depois de n - 1 rodadas todos os caminhos minimos (sem ciclo) estao relaxados
+        for _ in 1..n {
+            let mut changed: bool = false;
+
+            for (u, v, weight) in self.edges() {
+                if let Some(cost) = distances[u] {
+                    if distances[v].is_none_or(|actual| cost + weight < actual) {
+                        distances[v] = Some(cost + weight);
+                        predecessors[v] = Some(u);
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        // Se ainda da para relaxar alguma aresta existe ciclo negativo
+        for (u, v, weight) in self.edges() {
+            if let (Some(cost_u), Some(cost_v)) = (distances[u], distances[v]) {
+                if cost_u + weight < cost_v {
+                    return Err("Graph has a negative cycle reachable from the source!");
+                }
+            }
+        }
+
+        Ok(ShortestPaths::new(src_idx, distances, predecessors))
+    }
+
+    // Bellman-Ford a partir de uma origem virtual ligada a todos os vertices com peso 0.
+    // Retorna as distancias, os predecessores e algum vertice ainda relaxado na n-esima rodada (se houver
+    // ciclo negativo).
+    fn relax_from_virtual_source(&self) -> (Vec<i64>, Vec<Option<usize>>, Option<usize>) {
+        let n: usize = self.num_vertices();
+        let mut distances: Vec<i64> = vec![0; n];
+        let mut predecessors: Vec<Option<usize>> = vec![None; n];
+        let mut last_relaxed: Option<usize> = None;
+
+        for _ in 0..n {
+            last_relaxed = None;
+
+            for (u, v, weight) in self.edges() {
+                if distances[u] + weight < distances[v] {
+                    distances[v] = distances[u] + weight;
+                    predecessors[v] = Some(u);
+                    last_relaxed = Some(v);
+                }
+            }
+
+            if last_relaxed.is_none() {
+                break;
+            }
+        }
+
+        (distances, predecessors, last_relaxed)
+    }
+
+    // Algum ciclo de custo negativo do grafo, como sequencia de vertices (sem repetir o primeiro no final)
+    pub fn get_negative_cycle(&self) -> Option<Vec<usize>> {
+        let (_, predecessors, last_relaxed) = self.relax_from_virtual_source();
+        let mut vertex_idx: usize = last_relaxed?;
+
+        // Voltando n vezes pelos predecessores com certeza se cai dentro do ciclo
+        for _ in 0..self.num_vertices() {
+            vertex_idx = predecessors[vertex_idx]?;
+        }
+
+        let mut cycle: Vec<usize> = vec![vertex_idx];
+        let mut actual_vertex: usize = predecessors[vertex_idx]?;
+
+        while actual_vertex != vertex_idx {
+            cycle.push(actual_vertex);
+            actual_vertex = predecessors[actual_vertex]?;
+        }
+
+        cycle.reverse();
+
+        Some(cycle)
+    }
+
+    // Algoritmo de Johnson: https://en.wikipedia.org/wiki/Johnson%27s_algorithm
+    // Os potenciais h calculados pelo Bellman-Ford tornam todos os pesos w(u, v) + h(u) - h(v) nao negativos,
+    // entao da para rodar um Dijkstra por vertice. Em grafos esparsos fica O(n·m·log n), bem abaixo do
+    // Floyd-Warshall. Retorna a arvore de caminhos minimos de cada origem, com os custos originais.
+    pub fn johnson(&self) -> Result<Vec<ShortestPaths<i64>>, &'static str> {
+        let (potentials, _, last_relaxed) = self.relax_from_virtual_source();
+
+        if last_relaxed.is_some() {
+            return Err("Graph has a negative cycle!");
+        }
+
+        let n: usize = self.num_vertices();
+        let mut all_paths: Vec<ShortestPaths<i64>> = Vec::with_capacity(n);
+
+        for src_idx in 0..n {
+            let mut distances: Vec<Option<i64>> = vec![None; n];
+            let mut predecessors: Vec<Option<usize>> = vec![None; n];
+            let mut is_closed: Vec<bool> = vec![false; n];
+            let mut heap: BinaryHeap<Reverse<(i64, usize)>> = BinaryHeap::new();
+
+            distances[src_idx] = Some(0);
+            heap.push(Reverse((0, src_idx)));
+
+            while let Some(Reverse((cost, u))) = heap.pop() {
+                if is_closed[u] {
+                    continue;
+                }
+
+                is_closed[u] = true;
+
+                for (v, weight) in self.neighbors(u)? {
+                    // Peso reponderado, sempre >= 0
+                    let total_cost: i64 = cost + weight + potentials[u] - potentials[v];
+
+                    if !is_closed[v] && distances[v].is_none_or(|actual| total_cost < actual) {
+                        distances[v] = Some(total_cost);
+                        predecessors[v] = Some(u);
+                        heap.push(Reverse((total_cost, v)));
+                    }
+                }
+            }
+
+            // Desfaz a reponderacao para voltar aos custos originais
+            let distances: Vec<Option<i64>> = distances.into_iter().enumerate()
+                .map(|(v, d)| d.map(|d| d - potentials[src_idx] + potentials[v]))
+                .collect();
+
+            all_paths.push(ShortestPaths::new(src_idx, distances, predecessors));
+        }
+
+        Ok(all_paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::next_random;
+
+    // Grafo esparso com pesos EUC_2D entre pontos proximos, onde o arredondamento faz diferenca
+    fn random_euclidean_graph(n: usize, seed: &mut u64) -> (Graph<usize>, Coordinates) {
+        let points: Vec<(f64, f64)> = (0..n).map(|_| ((next_random(seed) % 40) as f64 / 10.0, (next_random(seed) % 40) as f64 / 10.0)).collect();
+        let coordinates: Coordinates = Coordinates::new(points, Metric::Euclidean);
+        let matrix: Vec<Vec<u64>> = (0..n)
+            .map(|i| (0..n).map(|j| if i != j && next_random(seed).is_multiple_of(3) { coordinates.distance(i, j).max(1) } else { 0 }).collect())
+            .collect();
+
+        (Graph::from((0..n).collect(), matrix), coordinates)
+    }
+
+    #[test]
+    fn dijkstra_known_answer() {
+        let t: Graph<usize> = Graph::from(vec![0, 1, 2, 3], vec![vec![0, 4, 1, 0], vec![0, 0, 0, 1], vec![0, 2, 0, 6], vec![0, 0, 0, 0]]);
+        let paths: ShortestPaths<u64> = t.get_dijkstra(0).unwrap();
+
+        assert_eq!(paths.get_distances(), &vec![Some(0), Some(3), Some(1), Some(4)]);
+        assert_eq!(paths.path_to(3), Some((vec![0, 2, 1, 3], 4)));
+        assert_eq!(t.get_dijkstra(3).unwrap().path_to(0), None);
+        assert_eq!(t.get_dijkstra_path(0, 0), Ok(Some((vec![0], 0))));
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let mut seed: u64 = 11;
+
+        for _ in 0..300 {
+            let (t, coordinates) = random_euclidean_graph(12, &mut seed);
+            let heuristic: EuclideanHeuristic = EuclideanHeuristic::new(&coordinates);
+            let (src, dst) = (next_random(&mut seed) as usize % 12, next_random(&mut seed) as usize % 12);
+            let expected: Option<u64> = t.get_dijkstra_path(src, dst).unwrap().map(|(_, cost)| cost);
+
+            assert_eq!(t.astar(src, dst, &heuristic).unwrap().map(|(_, cost)| cost), expected);
+
+            // Estimativa admissivel mas inconsistente: uma fracao aleatoria da distancia real
+            let distances: Vec<Option<u64>> = (0..12).map(|v| t.get_dijkstra_path(v, dst).unwrap().map(|(_, cost)| cost)).collect();
+            let fractions: Vec<u64> = (0..12).map(|_| next_random(&mut seed) % 5).collect();
+            let inconsistent = |v: usize, _: usize| -> u64 { distances[v].unwrap_or(0) * fractions[v] / 4 };
+
+            assert_eq!(t.astar(src, dst, &inconsistent).unwrap().map(|(_, cost)| cost), expected);
+        }
+    }
+
+    #[test]
+    fn heuristics_never_overestimate() {
+        let points: Vec<(f64, f64)> = vec![(0.0, 0.0), (1.4, 0.0), (2.8, 0.0), (4.2, 0.0)];
+        let coordinates: Coordinates = Coordinates::new(points.clone(), Metric::Euclidean);
+        let t: Graph<usize> = Graph::from_coordinates(vec![0, 1, 2, 3], Coordinates::new(points, Metric::Euclidean)).unwrap();
+
+        // 0 -> 1 -> 2 -> 3 custa 1 + 1 + 1 = 3, menos que a linha reta 4.2 arredondada para baixo
+        assert!(EuclideanHeuristic::new(&coordinates).estimate(0, 3) <= 3);
+        assert_eq!(t.astar(0, 3, t.get_coordinate_heuristic().unwrap().as_ref()).unwrap(), Some((vec![0, 1, 2, 3], 3)));
+
+        let ceil: Coordinates = Coordinates::new(vec![(0.0, 0.0), (3.0, 4.0)], Metric::EuclideanCeil);
+        assert_eq!(EuclideanHeuristic::new(&ceil).estimate(0, 1), 5);
+    }
+
+    #[test]
+    fn metric_closure_expands_routes() {
+        let t: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0, 1, 9], vec![1, 0, 1], vec![9, 1, 0]]);
+        let closure: MetricClosure = t.get_metric_closure();
+
+        assert_eq!(closure.get_distances()[0][2], Some(2));
+        assert_eq!(closure.expand_route(&[0, 2]), Ok(vec![0, 1, 2, 1]));
+        assert_eq!(closure.expand_route(&[1]), Ok(vec![1]));
+        assert_eq!(closure.expand_route(&[]), Ok(Vec::new()));
+    }
+}
@@ -0,0 +1,200 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use crate::graph::Graph;
+
+// Grafo direcionado com pesos com sinal (pedagios, descontos). Diferente do Graph, que usa 0 como
+// "sem aresta", aqui as arestas ficam em listas de adjacencia e podem ter peso 0 ou negativo.
+pub struct SignedGraph<V: Eq + PartialEq + Hash + Display + Clone> {
+    vertices: Vec<V>,
+    adjacency: Vec<Vec<(usize, i64)>>
+}
+
+impl<V: Eq + Hash + Display + Clone> Default for SignedGraph<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone> SignedGraph<V> {
+    pub fn new() -> Self {
+        SignedGraph {
+            vertices: Vec::<V>::new(),
+            adjacency: Vec::<Vec<(usize, i64)>>::new()
+        }
+    }
+
+    // Copia as arestas (peso nao nulo) de um Graph
+    pub fn from_graph(graph: &Graph<V>) -> Result<Self, &'static str> {
+        let mut signed_graph: SignedGraph<V> = SignedGraph::new();
+
+        for (_, vertex) in graph.vertices() {
+            signed_graph.insert_vertex(vertex.clone());
+        }
+
+        for edge in graph.edges() {
+            let weight: i64 = match i64::try_from(edge.get_weight()) {
+                Ok(w) => w,
+                Err(_) => return Err("Edge weight does not fit in i64!")
+            };
+
+            signed_graph.insert_edge(*edge.get_src(), *edge.get_dst(), weight)?;
+        }
+
+        Ok(signed_graph)
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.adjacency.iter().map(|list| list.len()).sum()
+    }
+
+    pub fn get_vertex(&self, vertex_idx: usize) -> Result<&V, &'static str> {
+        if self.vertices.len() <= vertex_idx {
+            return Err("Index out of range!");
+        }
+
+        Ok(&self.vertices[vertex_idx])
+    }
+
+    pub fn insert_vertex(&mut self, vertex: V) -> usize {
+        self.vertices.push(vertex);
+        self.adjacency.push(Vec::new());
+
+        self.vertices.len() - 1
+    }
+
+    // Insere o arco src -> dst, substituindo o peso se ele ja existir
+    pub fn insert_edge(&mut self, src_idx: usize, dst_idx: usize, edge_weight: i64) -> Result<(), &'static str> {
+        if self.vertices.len() <= src_idx || self.vertices.len() <= dst_idx {
+            return Err("Index out of range!");
+        }
+
+        match self.adjacency[src_idx].iter_mut().find(|(dst, _)| *dst == dst_idx) {
+            Some(edge) => edge.1 = edge_weight,
+            None => self.adjacency[src_idx].push((dst_idx, edge_weight))
+        }
+
+        Ok(())
+    }
+
+    pub fn remove_edge(&mut self, src_idx: usize, dst_idx: usize) -> Result<(), &'static str> {
+        if self.vertices.len() <= src_idx || self.vertices.len() <= dst_idx {
+            return Err("Index out of range!");
+        }
+
+        self.adjacency[src_idx].retain(|(dst, _)| *dst != dst_idx);
+
+        Ok(())
+    }
+
+    // None quando nao existe o arco
+    pub fn get_edge_weight(&self, src_idx: usize, dst_idx: usize) -> Result<Option<i64>, &'static str> {
+        if self.vertices.len() <= src_idx || self.vertices.len() <= dst_idx {
+            return Err("Index out of range!");
+        }
+
+        Ok(self.adjacency[src_idx].iter().find(|(dst, _)| *dst == dst_idx).map(|(_, w)| *w))
+    }
+
+    // Pares (vizinho, peso) dos arcos que saem do vertice
+    pub fn neighbors(&self, vertex_idx: usize) -> Result<impl Iterator<Item = (usize, i64)> + '_, &'static str> {
+        if self.vertices.len() <= vertex_idx {
+            return Err("Index out of range!");
+        }
+
+        Ok(self.adjacency[vertex_idx].iter().copied())
+    }
+
+    // Todos os arcos como (src, dst, peso)
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, i64)> + '_ {
+        self.adjacency.iter().enumerate().flat_map(|(src, list)| list.iter().map(move |&(dst, w)| (src, dst, w)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::next_random;
+    use crate::shortest_paths::ShortestPaths;
+
+    fn signed_graph(n: usize, arcs: &[(usize, usize, i64)]) -> SignedGraph<usize> {
+        let mut t: SignedGraph<usize> = SignedGraph::new();
+
+        for v in 0..n {
+            t.insert_vertex(v);
+        }
+
+        for &(u, v, w) in arcs {
+            t.insert_edge(u, v, w).unwrap();
+        }
+
+        t
+    }
+
+    #[test]
+    fn bellman_ford_known_answer() {
+        // O desconto de 1 para 2 faz o caminho 0 -> 1 -> 2 -> 3 ser o mais barato
+        let t: SignedGraph<usize> = signed_graph(4, &[(0, 1, 4), (0, 2, 2), (1, 2, -3), (2, 3, 2), (1, 3, 5)]);
+        let paths: ShortestPaths<i64> = t.bellman_ford(0).unwrap();
+
+        assert_eq!(paths.get_distances(), &vec![Some(0), Some(4), Some(1), Some(3)]);
+        assert_eq!(paths.path_to(3), Some((vec![0, 1, 2, 3], 3)));
+        assert_eq!(t.bellman_ford(3).unwrap().path_to(0), None);
+    }
+
+    #[test]
+    fn zero_weight_arcs_are_edges() {
+        let t: SignedGraph<usize> = signed_graph(2, &[(0, 1, 0)]);
+
+        assert_eq!(t.get_edge_weight(0, 1), Ok(Some(0)));
+        assert_eq!(t.get_edge_weight(1, 0), Ok(None));
+        assert_eq!(t.bellman_ford(0).unwrap().path_to(1), Some((vec![0, 1], 0)));
+    }
+
+    #[test]
+    fn single_vertex() {
+        let t: SignedGraph<usize> = signed_graph(1, &[]);
+
+        assert_eq!(t.bellman_ford(0).unwrap().get_distances(), &vec![Some(0)]);
+        assert_eq!(t.johnson().unwrap()[0].get_distances(), &vec![Some(0)]);
+        assert_eq!(t.get_negative_cycle(), None);
+    }
+
+    #[test]
+    fn negative_cycles_are_reported() {
+        let t: SignedGraph<usize> = signed_graph(4, &[(0, 1, 1), (1, 2, -2), (2, 3, 1), (3, 1, -1)]);
+        let mut cycle: Vec<usize> = t.get_negative_cycle().unwrap();
+        cycle.sort_unstable();
+
+        assert_eq!(cycle, vec![1, 2, 3]);
+        assert!(t.bellman_ford(0).is_err());
+        assert!(t.johnson().is_err());
+    }
+
+    #[test]
+    fn johnson_matches_bellman_ford() {
+        let mut seed: u64 = 17;
+
+        for _ in 0..100 {
+            // Pesos w + p(u) - p(v) com w >= 0 podem ser negativos sem formar ciclo negativo
+            let potentials: Vec<i64> = (0..7).map(|_| (next_random(&mut seed) % 10) as i64).collect();
+            let mut arcs: Vec<(usize, usize, i64)> = Vec::new();
+
+            for (u, v) in (0..7).flat_map(|u| (0..7).map(move |v| (u, v))) {
+                if u != v && next_random(&mut seed).is_multiple_of(3) {
+                    arcs.push((u, v, (next_random(&mut seed) % 5) as i64 + potentials[u] - potentials[v]));
+                }
+            }
+
+            let t: SignedGraph<usize> = signed_graph(7, &arcs);
+            let all_paths: Vec<ShortestPaths<i64>> = t.johnson().unwrap();
+
+            for (src, paths) in all_paths.iter().enumerate() {
+                assert_eq!(paths.get_distances(), t.bellman_ford(src).unwrap().get_distances());
+            }
+        }
+    }
+}
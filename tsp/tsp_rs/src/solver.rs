@@ -0,0 +1,183 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use crate::graph::Graph;
+use crate::objective::Objective;
+
+// Algoritmos disponiveis para resolver o TSP, com o nome usado na linha de comando
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Solver {
+    BruteForce,
+    HeldKarp,
+    NearestNeighbor,
+    CheapestInsertion,
+    TwoOpt,
+    ThreeOpt,
+    OrOpt,
+    Christofides,
+    AssignmentPatching
+}
+
+impl Solver {
+    pub const ALL: [Solver; 9] = [
+        Solver::BruteForce,
+        Solver::HeldKarp,
+        Solver::NearestNeighbor,
+        Solver::CheapestInsertion,
+        Solver::TwoOpt,
+        Solver::ThreeOpt,
+        Solver::OrOpt,
+        Solver::Christofides,
+        Solver::AssignmentPatching
+    ];
+
+    pub fn from_name(name: &str) -> Option<Solver> {
+        Solver::ALL.into_iter().find(|solver| solver.get_name() == name)
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Solver::BruteForce => "exact",
+            Solver::HeldKarp => "held-karp",
+            Solver::NearestNeighbor => "nn",
+            Solver::CheapestInsertion => "insertion",
+            Solver::TwoOpt => "2opt",
+            Solver::ThreeOpt => "3opt",
+            Solver::OrOpt => "oropt",
+            Solver::Christofides => "christofides",
+            Solver::AssignmentPatching => "patching"
+        }
+    }
+
+    // Se o algoritmo funciona com matrizes assimetricas (ATSP). O 2-opt inverte trechos da rota e o
+    // Christofides depende de arestas nao direcionadas, entao os dois exigem uma matriz simetrica.
+    pub fn supports_asymmetric(&self) -> bool {
+        !matches!(self, Solver::TwoOpt | Solver::Christofides)
+    }
+}
+
+impl Display for Solver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.get_name())
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Executa o algoritmo escolhido, recusando antes grafos incompletos e grafos assimetricos que ele nao suporta
+    pub fn solve(&self, solver: Solver, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        self.check_complete()?;
+
+        if !solver.supports_asymmetric() && !self.is_symmetric() {
+            return Err("Solver does not support asymmetric graphs!");
+        }
+
+        match solver {
+            Solver::BruteForce => self.tsp_brute_force(log),
+            Solver::HeldKarp => self.tsp_held_karp(Objective::Length, log),
+            Solver::NearestNeighbor => {
+                let route: Vec<usize> = self.tsp_nearest_neighbor_greedy()?;
+                let cost: u64 = self.get_route_cost(&route)?;
+
+                if log {
+                    println!("Route cost: {} - Route found: {}", cost, route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
+                }
+
+                Ok((route, cost))
+            },
+            Solver::CheapestInsertion => self.tsp_cheapest_insertion(log),
+            Solver::TwoOpt => self.tsp_2_opt_approx(log),
+            Solver::ThreeOpt => self.tsp_3_opt_approx(log),
+            Solver::OrOpt => self.tsp_or_opt_approx(log),
+            Solver::Christofides => self.tsp_christofides_approx(log),
+            Solver::AssignmentPatching => self.tsp_assignment_patching(log)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::random_graph;
+    use itertools::Itertools;
+    use crate::path::RouteMode;
+
+    fn is_tour(route: &[usize], n: usize) -> bool {
+        route.iter().copied().sorted().eq(0..n)
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for solver in Solver::ALL {
+            assert_eq!(Solver::from_name(&solver.to_string()), Some(solver));
+        }
+
+        assert_eq!(Solver::from_name("simulated-annealing"), None);
+    }
+
+    #[test]
+    fn exact_solvers_agree() {
+        let mut seed: u64 = 31;
+
+        for n in [2, 3, 5, 7] {
+            for symmetric in [true, false] {
+                let t: Graph<usize> = random_graph(n, symmetric, &mut seed);
+                let (brute_route, brute_cost) = t.solve(Solver::BruteForce, false).unwrap();
+
+                assert!(is_tour(&brute_route, n));
+                assert_eq!(t.solve(Solver::HeldKarp, false).unwrap().1, brute_cost);
+            }
+        }
+    }
+
+    #[test]
+    fn heuristics_return_valid_tours() {
+        let mut seed: u64 = 37;
+        let heuristics: [Solver; 5] = [Solver::NearestNeighbor, Solver::CheapestInsertion, Solver::ThreeOpt, Solver::OrOpt, Solver::AssignmentPatching];
+
+        for n in [3, 6, 9] {
+            for symmetric in [true, false] {
+                let t: Graph<usize> = random_graph(n, symmetric, &mut seed);
+                let optimum: u64 = t.solve(Solver::HeldKarp, false).unwrap().1;
+
+                for solver in heuristics.iter().copied().chain(symmetric.then_some(Solver::Christofides)) {
+                    let (route, cost) = t.solve(solver, false).unwrap();
+
+                    assert!(is_tour(&route, n), "{}", solver);
+                    assert_eq!(t.get_route_cost(&route), Ok(cost));
+                    assert!(cost >= optimum);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn symmetric_only_solvers_reject_asymmetric_graphs() {
+        let t: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0, 1, 5], vec![5, 0, 1], vec![1, 5, 0]]);
+
+        for solver in Solver::ALL {
+            assert_eq!(t.solve(solver, false).is_err(), !solver.supports_asymmetric(), "{}", solver);
+        }
+    }
+
+    #[test]
+    fn solvers_reject_one_way_cycles() {
+        // A unica rota real é 0 -> 1 -> 2 -> 3 -> 0, com custo 20; os zeros sao arcos que nao existem
+        let t: Graph<usize> = Graph::from((0..4).collect(), vec![
+            vec![0, 5, 0, 0],
+            vec![0, 0, 5, 0],
+            vec![0, 0, 0, 5],
+            vec![5, 0, 0, 0]
+        ]);
+        let path: RouteMode = RouteMode::Path { start: None, end: None };
+
+        assert!(t.is_strongly_connected());
+
+        for solver in Solver::ALL {
+            assert_eq!(t.solve(solver, false), Err("Graph is not complete!"), "{}", solver);
+            assert_eq!(t.solve_with_mode(solver, path, false), Err("Graph is not complete!"), "{}", solver);
+            assert_eq!(t.solve_with_objective(solver, Objective::Bottleneck, false), Err("Graph is not complete!"), "{}", solver);
+        }
+
+        assert_eq!(t.tsp_nearest_neighbor_greedy(), Err("Graph is not complete!"));
+        assert_eq!(t.tsp_node_doubling(Solver::HeldKarp, false), Err("Graph is not complete!"));
+    }
+}
@@ -0,0 +1,31 @@
+use itertools::Itertools;
+use crate::graph::Graph;
+use crate::gtsp::Clusters;
+
+// Utilitarios compartilhados pelos testes dos modulos
+
+// Gerador congruencial linear, para os testes serem reprodutiveis sem depender de uma crate de aleatoriedade
+pub fn next_random(seed: &mut u64) -> u64 {
+    *seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    *seed >> 33
+}
+
+// Matriz completa com pesos de 1 a 30 fora da diagonal; se `symmetric`, o peso de (i, j) é o de (j, i)
+pub fn random_graph(n: usize, symmetric: bool, seed: &mut u64) -> Graph<usize> {
+    let mut matrix: Vec<Vec<u64>> = vec![vec![0; n]; n];
+
+    for (i, j) in (0..n).flat_map(|i| (0..n).map(move |j| (i, j))).filter(|(i, j)| i != j) {
+        matrix[i][j] = if symmetric && j < i { matrix[j][i] } else { 1 + next_random(seed) % 30 };
+    }
+
+    Graph::from((0..n).collect(), matrix)
+}
+
+// Menor rota entre todas as escolhas de um vertice por grupo e todas as ordens
+pub fn brute_force_gtsp(t: &Graph<usize>, clusters: &Clusters) -> u64 {
+    (0..clusters.num_clusters()).map(|c| clusters.get_members(c).clone()).multi_cartesian_product()
+        .flat_map(|chosen| chosen.iter().copied().permutations(chosen.len()).collect::<Vec<Vec<usize>>>())
+        .map(|route| t.get_route_cost(&route).unwrap())
+        .min()
+        .unwrap()
+}
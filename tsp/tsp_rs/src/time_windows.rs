@@ -0,0 +1,392 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::time::Instant;
+use crate::graph::Graph;
+
+// Janela de atendimento de uma cidade: o servico pode comecar entre `earliest` e `latest` e dura `service`.
+// Chegar antes de `earliest` obriga a esperar; comecar depois de `latest` é um atraso.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeWindow {
+    earliest: u64,
+    latest: u64,
+    service: u64
+}
+
+impl TimeWindow {
+    pub fn new(earliest: u64, latest: u64, service: u64) -> Result<Self, &'static str> {
+        if latest < earliest {
+            return Err("Time window closes before it opens!");
+        }
+
+        Ok(TimeWindow {
+            earliest,
+            latest,
+            service
+        })
+    }
+
+    pub fn get_earliest(&self) -> u64 {
+        self.earliest
+    }
+
+    pub fn get_latest(&self) -> u64 {
+        self.latest
+    }
+
+    pub fn get_service(&self) -> u64 {
+        self.service
+    }
+}
+
+// Horarios de uma visita da rota
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Visit {
+    vertex: usize,
+    arrival: u64,
+    wait: u64,
+    start: u64,
+    departure: u64,
+    lateness: u64
+}
+
+impl Visit {
+    pub fn get_vertex(&self) -> usize {
+        self.vertex
+    }
+
+    pub fn get_arrival(&self) -> u64 {
+        self.arrival
+    }
+
+    pub fn get_wait(&self) -> u64 {
+        self.wait
+    }
+
+    pub fn get_start(&self) -> u64 {
+        self.start
+    }
+
+    pub fn get_departure(&self) -> u64 {
+        self.departure
+    }
+
+    pub fn get_lateness(&self) -> u64 {
+        self.lateness
+    }
+}
+
+// Agenda de uma rota: sai do deposito (route[0]) na abertura da sua janela, visita as cidades na ordem e volta
+// ao deposito, que precisa ser alcancado antes do fechamento da janela dele
+pub struct Schedule {
+    visits: Vec<Visit>,
+    return_arrival: u64,
+    return_lateness: u64,
+    travel_cost: u64
+}
+
+impl Schedule {
+    pub fn get_visits(&self) -> &Vec<Visit> {
+        &self.visits
+    }
+
+    pub fn get_return_arrival(&self) -> u64 {
+        self.return_arrival
+    }
+
+    pub fn get_travel_cost(&self) -> u64 {
+        self.travel_cost
+    }
+
+    pub fn get_total_wait(&self) -> u64 {
+        self.visits.iter().map(|v| v.wait).sum()
+    }
+
+    pub fn get_total_lateness(&self) -> u64 {
+        self.visits.iter().map(|v| v.lateness).sum::<u64>() + self.return_lateness
+    }
+
+    // Cidades atendidas depois do fechamento da janela (o deposito aparece se a volta atrasar)
+    pub fn get_violations(&self) -> Vec<usize> {
+        let mut violations: Vec<usize> = self.visits.iter().filter(|v| v.lateness > 0).map(|v| v.vertex).collect();
+
+        if self.return_lateness > 0 && !self.visits.is_empty() {
+            violations.push(self.visits[0].vertex);
+        }
+
+        violations
+    }
+
+    pub fn is_feasible(&self) -> bool {
+        self.get_total_lateness() == 0
+    }
+
+    // Custo de viagem mais `lateness_penalty` por unidade de atraso, o objetivo do `tsp_time_windows`
+    pub fn get_penalized_cost(&self, lateness_penalty: u64) -> u64 {
+        self.travel_cost + lateness_penalty * self.get_total_lateness()
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Calcula chegada, espera, inicio, saida e atraso de cada visita, usando os pesos como tempos de viagem
+    pub fn get_schedule(&self, route: &[usize], windows: &[TimeWindow]) -> Result<Schedule, &'static str> {
+        if windows.len() != self.num_vertices() {
+            return Err("There must be one time window per vertex!");
+        }
+
+        if route.is_empty() {
+            return Err("Route is empty!");
+        }
+
+        if route.iter().any(|&v| self.num_vertices() <= v) {
+            return Err("Index out of range!");
+        }
+
+        let depot: usize = route[0];
+        let mut visits: Vec<Visit> = Vec::with_capacity(route.len());
+        let mut time: u64 = windows[depot].earliest;
+        let mut previous: usize = depot;
+
+        for (i, &vertex) in route.iter().enumerate() {
+            let arrival: u64 = if i == 0 { time } else { time + self.weight(previous, vertex) };
+            let window: &TimeWindow = &windows[vertex];
+            let start: u64 = arrival.max(window.earliest);
+
+            visits.push(Visit {
+                vertex,
+                arrival,
+                wait: start - arrival,
+                start,
+                departure: start + window.service,
+                lateness: start.saturating_sub(window.latest)
+            });
+
+            time = start + window.service;
+            previous = vertex;
+        }
+
+        let return_arrival: u64 = time + self.weight(previous, depot);
+
+        Ok(Schedule {
+            visits,
+            return_arrival,
+            return_lateness: return_arrival.saturating_sub(windows[depot].latest),
+            travel_cost: self.get_route_cost(route)?
+        })
+    }
+
+    // Objetivo penalizado sem montar a agenda, usado dentro da busca local
+    fn penalized_route_cost(&self, route: &[usize], windows: &[TimeWindow], lateness_penalty: u64) -> u64 {
+        let depot: usize = route[0];
+        let mut time: u64 = windows[depot].earliest + windows[depot].service;
+        let mut travel: u64 = 0;
+        let mut lateness: u64 = 0;
+
+        for pair in route.windows(2) {
+            let travel_time: u64 = self.weight(pair[0], pair[1]);
+            let start: u64 = (time + travel_time).max(windows[pair[1]].earliest);
+
+            travel += travel_time;
+            lateness += start.saturating_sub(windows[pair[1]].latest);
+            time = start + windows[pair[1]].service;
+        }
+
+        let back: u64 = self.weight(route[route.len() - 1], depot);
+        lateness += (time + back).saturating_sub(windows[depot].latest);
+
+        travel + back + lateness_penalty * lateness
+    }
+
+    // TSP com janelas de tempo a partir do deposito 0. Constroi a rota inserindo as cidades em ordem de
+    // fechamento da janela, cada uma na posicao de menor custo, e depois aplica busca local (mover segmentos
+    // de 1 a 3 cidades e trocar duas cidades) ate um otimo local. O objetivo é o tempo de viagem mais
+    // `lateness_penalty` por unidade de atraso, entao com uma penalidade alta a busca prioriza eliminar atrasos.
+    pub fn tsp_time_windows(&self, windows: &[TimeWindow], lateness_penalty: u64, log: bool) -> Result<(Vec<usize>, Schedule), &'static str> {
+        self.check_complete()?;
+
+        if self.num_vertices() == 0 {
+            return Err("Graph is empty!");
+        }
+
+        if windows.len() != self.num_vertices() {
+            return Err("There must be one time window per vertex!");
+        }
+
+        let start_time = Instant::now();
+
+        let n: usize = self.num_vertices();
+        let cost = |route: &[usize]| -> u64 { self.penalized_route_cost(route, windows, lateness_penalty) };
+
+        // Insercao
+        let mut pending: Vec<usize> = (1..n).collect();
+        pending.sort_by_key(|&v| (windows[v].latest, windows[v].earliest));
+
+        let mut route: Vec<usize> = vec![0];
+
+        for v in pending {
+            let best_position: usize = (1..=route.len())
+                .min_by_key(|&position| {
+                    let mut candidate: Vec<usize> = route.clone();
+                    candidate.insert(position, v);
+                    cost(&candidate)
+                })
+                .unwrap();
+
+            route.insert(best_position, v);
+        }
+
+        if log {
+            println!("Insertion route cost: {}", cost(&route));
+        }
+
+        // Busca local, sempre com o deposito na posicao 0
+        let mut best_cost: u64 = cost(&route);
+        let mut counter: usize = 0;
+
+        'search: loop {
+            counter += 1;
+
+            for segment_len in 1..=3 {
+                for i in 1..n.saturating_sub(segment_len - 1) {
+                    let mut rest: Vec<usize> = route.clone();
+                    let segment: Vec<usize> = rest.drain(i..i + segment_len).collect();
+
+                    for position in (1..=rest.len()).filter(|&p| p != i) {
+                        let mut candidate: Vec<usize> = rest.clone();
+                        candidate.splice(position..position, segment.iter().copied());
+                        let candidate_cost: u64 = cost(&candidate);
+
+                        if candidate_cost < best_cost {
+                            best_cost = candidate_cost;
+                            route = candidate;
+                            continue 'search;
+                        }
+                    }
+                }
+            }
+
+            for i in 1..n {
+                for j in i + 1..n {
+                    route.swap(i, j);
+                    let candidate_cost: u64 = cost(&route);
+
+                    if candidate_cost < best_cost {
+                        best_cost = candidate_cost;
+                        continue 'search;
+                    }
+
+                    route.swap(i, j);
+                }
+            }
+
+            break;
+        }
+
+        let schedule: Schedule = self.get_schedule(&route, windows)?;
+
+        let end_time = Instant::now();
+
+        if log {
+            println!("Time elapsed: {} - Total iterations: {} - Travel cost: {} - Total lateness: {} - Route found: {}", end_time.duration_since(start_time).as_micros(), counter, schedule.get_travel_cost(), schedule.get_total_lateness(), route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
+        }
+
+        Ok((route, schedule))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_graph(n: usize) -> Graph<usize> {
+        Graph::from((0..n).collect(), (0..n as u64).map(|i| (0..n as u64).map(|j| i.abs_diff(j)).collect()).collect())
+    }
+
+    #[test]
+    fn time_window_must_be_ordered() {
+        assert!(TimeWindow::new(5, 4, 0).is_err());
+        assert!(TimeWindow::new(5, 5, 0).is_ok());
+    }
+
+    #[test]
+    fn schedule_known_answer() {
+        let t: Graph<usize> = line_graph(3);
+        let windows: Vec<TimeWindow> = vec![TimeWindow::new(0, 100, 0).unwrap(), TimeWindow::new(5, 10, 2).unwrap(), TimeWindow::new(0, 3, 1).unwrap()];
+        let schedule: Schedule = t.get_schedule(&[0, 1, 2], &windows).unwrap();
+        let visits: &Vec<Visit> = schedule.get_visits();
+
+        // Chega em 1 no instante 1 e espera ate 5, chega em 2 no instante 8 com 5 de atraso
+        assert_eq!((visits[1].get_arrival(), visits[1].get_wait(), visits[1].get_start(), visits[1].get_departure()), (1, 4, 5, 7));
+        assert_eq!((visits[2].get_arrival(), visits[2].get_start(), visits[2].get_lateness()), (8, 8, 5));
+        assert_eq!(schedule.get_return_arrival(), 11);
+        assert_eq!(schedule.get_travel_cost(), 4);
+        assert_eq!(schedule.get_total_wait(), 4);
+        assert_eq!(schedule.get_violations(), vec![2]);
+        assert!(!schedule.is_feasible());
+        assert_eq!(schedule.get_penalized_cost(10), 54);
+
+        assert!(t.get_schedule(&[0, 1, 3], &windows).is_err());
+        assert!(t.get_schedule(&[0, 1, 2], &windows[..2]).is_err());
+    }
+
+    #[test]
+    fn late_return_to_depot_is_a_violation() {
+        let t: Graph<usize> = line_graph(2);
+        let windows: Vec<TimeWindow> = vec![TimeWindow::new(0, 1, 0).unwrap(), TimeWindow::new(0, 10, 3).unwrap()];
+        let schedule: Schedule = t.get_schedule(&[0, 1], &windows).unwrap();
+
+        assert_eq!(schedule.get_return_arrival(), 5);
+        assert_eq!(schedule.get_total_lateness(), 4);
+        assert_eq!(schedule.get_violations(), vec![0]);
+    }
+
+    #[test]
+    fn windows_force_the_only_feasible_order() {
+        // Com tempos de viagem de no maximo 5, so a ordem 0 -> 3 -> 1 -> 2 respeita todas as janelas
+        let t: Graph<usize> = Graph::from((0..4).collect(), vec![
+            vec![0, 4, 2, 3],
+            vec![4, 0, 5, 1],
+            vec![2, 5, 0, 4],
+            vec![3, 1, 4, 0]
+        ]);
+        let windows: Vec<TimeWindow> = vec![
+            TimeWindow::new(0, 1000, 0).unwrap(),
+            TimeWindow::new(10, 20, 0).unwrap(),
+            TimeWindow::new(30, 40, 0).unwrap(),
+            TimeWindow::new(0, 5, 0).unwrap()
+        ];
+        let (route, schedule) = t.tsp_time_windows(&windows, 1000, false).unwrap();
+
+        assert_eq!(route, vec![0, 3, 1, 2]);
+        assert!(schedule.is_feasible());
+        assert_eq!(schedule.get_travel_cost(), 3 + 1 + 5 + 2);
+    }
+
+    #[test]
+    fn open_windows_give_a_valid_tour() {
+        let t: Graph<usize> = line_graph(6);
+        let windows: Vec<TimeWindow> = vec![TimeWindow::new(0, u64::MAX / 2, 1).unwrap(); 6];
+        let (mut route, schedule) = t.tsp_time_windows(&windows, 1, false).unwrap();
+
+        assert_eq!(route[0], 0);
+        assert!(schedule.is_feasible());
+        assert_eq!(schedule.get_travel_cost(), 10);
+
+        route.sort();
+        assert_eq!(route, (0..6).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn small_graphs() {
+        let single: Graph<usize> = Graph::from(vec![0], vec![vec![0]]);
+        let pair: Graph<usize> = Graph::from(vec![0, 1], vec![vec![0, 3], vec![7, 0]]);
+        let window: TimeWindow = TimeWindow::new(0, 100, 0).unwrap();
+
+        let (route, schedule) = single.tsp_time_windows(&[window], 1, false).unwrap();
+        assert_eq!((route, schedule.get_travel_cost()), (vec![0], 0));
+
+        let (route, schedule) = pair.tsp_time_windows(&[window, window], 1, false).unwrap();
+        assert_eq!((route, schedule.get_travel_cost(), schedule.get_return_arrival()), (vec![0, 1], 10, 10));
+
+        assert!(pair.tsp_time_windows(&[window], 1, false).is_err());
+    }
+}
@@ -0,0 +1,314 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::time::Instant;
+use crate::graph::Graph;
+use crate::gtsp::Clusters;
+use crate::solver::Solver;
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Transformacao ATSP -> STSP de Jonker e Volgenant (node doubling). Cada cidade i vira duas: a entrada i e a
+    // saida n + i, ligadas por uma aresta de peso 1. O arco i -> j vira a aresta nao direcionada (n + i, j) com
+    // peso d(i, j) + M, e entradas com entradas ou saidas com saidas custam 2M. Com M maior que qualquer rota,
+    // as rotas mais baratas usam todas as n ligacoes i - (n + i) e alternam entrada e saida, entao cada uma
+    // corresponde a uma rota do grafo original com custo n·(M + 1) a mais. Os rotulos sao os indices 0..2n.
+    pub fn get_node_doubling(&self) -> Result<Graph<usize>, &'static str> {
+        self.check_complete()?;
+
+        let n: usize = self.num_vertices();
+
+        if n == 0 {
+            return Err("Graph is empty!");
+        }
+
+        let max_weight: u64 = (0..n).flat_map(|i| (0..n).map(move |j| (i, j))).map(|(i, j)| self.weight(i, j)).max().unwrap();
+        let m: u64 = match max_weight.checked_mul(n as u64 + 1).and_then(|w| w.checked_add(n as u64 + 1)) {
+            Some(m) if m.checked_mul(4).is_some() => m,
+            _ => return Err("Edge weights are too large for the transformation!")
+        };
+
+        let mut matrix: Vec<Vec<u64>> = vec![vec![0; 2 * n]; 2 * n];
+
+        for i in 0..n {
+            matrix[i][n + i] = 1;
+            matrix[n + i][i] = 1;
+
+            for j in (0..n).filter(|&j| j != i) {
+                matrix[n + i][j] = self.weight(i, j) + m;
+                matrix[j][n + i] = self.weight(i, j) + m;
+                matrix[i][j] = 2 * m;
+                matrix[n + i][n + j] = 2 * m;
+            }
+        }
+
+        Ok(Graph::from((0..2 * n).collect(), matrix))
+    }
+
+    // Converte uma rota do grafo dobrado de volta para este grafo: cada cidade entra na ordem da sua primeira
+    // copia visitada. A rota pode ter sido percorrida em qualquer sentido, entao testa os dois e fica com o
+    // mais barato. Retorna a rota com o custo real no grafo original.
+    pub fn route_from_node_doubling(&self, doubled_route: &[usize]) -> Result<(Vec<usize>, u64), &'static str> {
+        let n: usize = self.num_vertices();
+
+        if doubled_route.len() != 2 * n {
+            return Err("Route does not visit every vertex!");
+        }
+
+        if doubled_route.iter().any(|&v| 2 * n <= v) {
+            return Err("Index out of range!");
+        }
+
+        let collapse = |route: &mut dyn Iterator<Item = &usize>| -> Vec<usize> {
+            let mut visited: Vec<bool> = vec![false; n];
+
+            route.map(|&v| v % n).filter(|&v| !std::mem::replace(&mut visited[v], true)).collect()
+        };
+
+        let forward: Vec<usize> = collapse(&mut doubled_route.iter());
+        let backward: Vec<usize> = collapse(&mut doubled_route.iter().rev());
+
+        if forward.len() != n {
+            return Err("Route does not visit every vertex!");
+        }
+
+        let forward_cost: u64 = self.get_route_cost(&forward)?;
+        let backward_cost: u64 = self.get_route_cost(&backward)?;
+
+        if backward_cost < forward_cost {
+            Ok((backward, backward_cost))
+        } else {
+            Ok((forward, forward_cost))
+        }
+    }
+
+    // Resolve uma instancia assimetrica com um algoritmo simetrico rodando sobre o grafo dobrado
+    pub fn tsp_node_doubling(&self, solver: Solver, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        let start_time = Instant::now();
+
+        let doubled: Graph<usize> = self.get_node_doubling()?;
+        let (doubled_route, _) = doubled.solve(solver, false)?;
+        let (route, cost) = self.route_from_node_doubling(&doubled_route)?;
+
+        let end_time = Instant::now();
+
+        if log {
+            println!("Time elapsed: {} - Solver: {} on {} vertices - Route cost: {} - Route found: {}", end_time.duration_since(start_time).as_micros(), solver, doubled.num_vertices(), cost, route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
+        }
+
+        Ok((route, cost))
+    }
+
+    // Transformacao GTSP -> ATSP de Noon e Bean. Os vertices de cada grupo formam um ciclo de arcos de peso 1 na
+    // ordem de `get_members` e o arco u -> w entre grupos diferentes vira d(next(u), w) + M, onde next(u) é o
+    // sucessor de u no ciclo do grupo. Com M maior que qualquer rota, as rotas mais baratas entram em cada grupo
+    // por um vertice v, percorrem o ciclo inteiro ate o antecessor de v e saem pagando d(v, w): correspondem a
+    // rota do GTSP que visita v, com custo m·M + (n - m) a mais. Arcos dentro do grupo fora do ciclo custam M.
+    // Os rotulos sao os indices dos vertices.
+    pub fn get_noon_bean(&self, clusters: &Clusters) -> Result<Graph<usize>, &'static str> {
+        self.check_clustered_instance(clusters)?;
+
+        let n: usize = self.num_vertices();
+        let successor = |u: usize| -> usize {
+            let members: &Vec<usize> = clusters.get_members(clusters.get_cluster_of(u));
+            let position: usize = members.iter().position(|&v| v == u).unwrap();
+
+            members[(position + 1) % members.len()]
+        };
+
+        let max_weight: u64 = (0..n).flat_map(|i| (0..n).map(move |j| (i, j))).map(|(i, j)| self.weight(i, j)).max().unwrap_or(0);
+        let m: u64 = match max_weight.checked_mul(clusters.num_clusters() as u64 + 1).and_then(|w| w.checked_add(n as u64 + 1)) {
+            Some(m) if m.checked_add(max_weight).and_then(|w| w.checked_mul(n as u64 + 1)).is_some() => m,
+            _ => return Err("Edge weights are too large for the transformation!")
+        };
+
+        let mut matrix: Vec<Vec<u64>> = vec![vec![0; n]; n];
+
+        for (u, row) in matrix.iter_mut().enumerate() {
+            let next: usize = successor(u);
+
+            for w in (0..n).filter(|&w| w != u) {
+                row[w] = if clusters.get_cluster_of(u) != clusters.get_cluster_of(w) {
+                    self.weight(next, w) + m
+                } else if w == next {
+                    1
+                } else {
+                    m
+                };
+            }
+        }
+
+        Ok(Graph::from((0..n).collect(), matrix))
+    }
+
+    // Converte uma rota do grafo de Noon-Bean de volta para o GTSP: cada arco u -> w entre grupos diferentes
+    // visita o sucessor de u no ciclo do grupo. Se um grupo sai mais de uma vez (rota que nao respeita a
+    // estrutura), vale a primeira saida. Retorna a rota com o custo real neste grafo.
+    pub fn route_from_noon_bean(&self, clusters: &Clusters, atsp_route: &[usize]) -> Result<(Vec<usize>, u64), &'static str> {
+        let n: usize = self.num_vertices();
+
+        if atsp_route.len() != n {
+            return Err("Route does not visit every vertex!");
+        }
+
+        if atsp_route.iter().any(|&v| n <= v) {
+            return Err("Index out of range!");
+        }
+
+        // Com um grupo so a rota nunca sai dele, e qualquer vertice sozinho custa 0
+        if clusters.num_clusters() == 1 {
+            return Ok((vec![atsp_route[0]], 0));
+        }
+
+        let mut visited: Vec<bool> = vec![false; clusters.num_clusters()];
+        let mut route: Vec<usize> = Vec::with_capacity(clusters.num_clusters());
+
+        for i in 0..n {
+            let (u, w) = (atsp_route[i], atsp_route[(i + 1) % n]);
+            let cluster: usize = clusters.get_cluster_of(u);
+
+            if cluster != clusters.get_cluster_of(w) && !visited[cluster] {
+                let members: &Vec<usize> = clusters.get_members(cluster);
+                let position: usize = members.iter().position(|&v| v == u).unwrap();
+
+                route.push(members[(position + 1) % members.len()]);
+                visited[cluster] = true;
+            }
+        }
+
+        if route.len() != clusters.num_clusters() {
+            return Err("Route does not visit every cluster!");
+        }
+
+        let cost: u64 = self.get_route_cost(&route)?;
+
+        Ok((route, cost))
+    }
+
+    // Resolve o GTSP com um algoritmo de TSP rodando sobre o grafo de Noon-Bean, que é assimetrico; algoritmos
+    // que so aceitam grafos simetricos passam tambem pelo node doubling
+    pub fn gtsp_noon_bean(&self, clusters: &Clusters, solver: Solver, log: bool) -> Result<(Vec<usize>, u64), &'static str> {
+        let start_time = Instant::now();
+
+        let atsp: Graph<usize> = self.get_noon_bean(clusters)?;
+        let (atsp_route, _) = if solver.supports_asymmetric() {
+            atsp.solve(solver, false)?
+        } else {
+            atsp.tsp_node_doubling(solver, false)?
+        };
+        let (route, cost) = self.route_from_noon_bean(clusters, &atsp_route)?;
+
+        let end_time = Instant::now();
+
+        if log {
+            println!("Time elapsed: {} - Solver: {} on the Noon-Bean graph - Route cost: {} - Route found: {}", end_time.duration_since(start_time).as_micros(), solver, cost, route.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" -> "));
+        }
+
+        Ok((route, cost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{brute_force_gtsp, next_random, random_graph};
+    use itertools::Itertools;
+
+    #[test]
+    fn node_doubling_known_answer() {
+        // Ida 0 -> 1 -> 2 -> 0 custa 3, a volta custa 30
+        let t: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0, 1, 10], vec![10, 0, 1], vec![1, 10, 0]]);
+        let doubled: Graph<usize> = t.get_node_doubling().unwrap();
+
+        assert_eq!(doubled.num_vertices(), 6);
+        assert!(doubled.is_symmetric());
+        assert_eq!(t.tsp_node_doubling(Solver::HeldKarp, false), Ok((vec![0, 1, 2], 3)));
+        assert_eq!(t.route_from_node_doubling(&[0, 3, 1, 4, 2, 5]), Ok((vec![0, 1, 2], 3)));
+        assert!(t.route_from_node_doubling(&[0, 3, 1, 4]).is_err());
+    }
+
+    #[test]
+    fn node_doubling_keeps_the_optimum() {
+        let mut seed: u64 = 41;
+
+        for n in [2, 3, 4, 6] {
+            for _ in 0..10 {
+                let t: Graph<usize> = random_graph(n, false, &mut seed);
+                let (route, cost) = t.tsp_node_doubling(Solver::HeldKarp, false).unwrap();
+
+                assert_eq!(cost, t.solve(Solver::BruteForce, false).unwrap().1);
+                assert_eq!(t.get_route_cost(&route), Ok(cost));
+
+                let (route, cost) = t.tsp_node_doubling(Solver::Christofides, false).unwrap();
+                assert!(route.iter().copied().sorted().eq(0..n));
+                assert_eq!(t.get_route_cost(&route), Ok(cost));
+            }
+        }
+    }
+
+    #[test]
+    fn node_doubling_small_graphs() {
+        let single: Graph<usize> = Graph::from(vec![0], vec![vec![0]]);
+        // Peso 0 fora da diagonal é falta de arco, nao um arco de graca
+        let zeros: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0; 3]; 3]);
+
+        assert!(Graph::<usize>::new().get_node_doubling().is_err());
+        assert_eq!(single.get_node_doubling().unwrap().get_adjacency_matrix(), &vec![vec![0, 1], vec![1, 0]]);
+        assert_eq!(single.tsp_node_doubling(Solver::HeldKarp, false), Ok((vec![0], 0)));
+        assert_eq!(zeros.tsp_node_doubling(Solver::HeldKarp, false), Err("Graph is not complete!"));
+    }
+
+    #[test]
+    fn noon_bean_known_answer() {
+        let t: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0, 4, 6], vec![4, 0, 5], vec![6, 5, 0]]);
+        let clusters: Clusters = Clusters::from_labels(&[0, 0, 1]).unwrap();
+        let atsp: Graph<usize> = t.get_noon_bean(&clusters).unwrap();
+        // M = 6 * (2 + 1) + 3 + 1
+        let m: u64 = 22;
+
+        assert_eq!(atsp.get_adjacency_matrix(), &vec![
+            vec![0, 1, 5 + m],
+            vec![1, 0, 6 + m],
+            vec![6 + m, 5 + m, 0]
+        ]);
+
+        // 0 -> 1 -> 2 entra no grupo por 0 e sai por 1, entao visita 0
+        assert_eq!(t.route_from_noon_bean(&clusters, &[0, 1, 2]), Ok((vec![0, 2], 12)));
+        assert_eq!(t.gtsp_noon_bean(&clusters, Solver::HeldKarp, false), Ok((vec![1, 2], 10)));
+        assert!(t.route_from_noon_bean(&clusters, &[0, 1]).is_err());
+        assert!(t.route_from_noon_bean(&clusters, &[0, 1, 3]).is_err());
+    }
+
+    #[test]
+    fn noon_bean_keeps_the_optimum() {
+        let mut seed: u64 = 48;
+
+        for n in [2, 3, 5, 7] {
+            for groups in [1, 2, n / 2 + 1, n] {
+                let t: Graph<usize> = random_graph(n, false, &mut seed);
+                let labels: Vec<u64> = (0..n).map(|v| if v < groups { v as u64 } else { next_random(&mut seed) % groups as u64 }).collect();
+                let clusters: Clusters = Clusters::from_labels(&labels).unwrap();
+                let (route, cost) = t.gtsp_noon_bean(&clusters, Solver::HeldKarp, false).unwrap();
+
+                assert!(clusters.accepts(&route));
+                assert_eq!(t.get_route_cost(&route), Ok(cost));
+                assert_eq!(cost, brute_force_gtsp(&t, &clusters));
+
+                // Christofides passa tambem pelo node doubling
+                let (route, cost) = t.gtsp_noon_bean(&clusters, Solver::Christofides, false).unwrap();
+                assert!(clusters.accepts(&route));
+                assert!(cost >= brute_force_gtsp(&t, &clusters));
+            }
+        }
+    }
+
+    #[test]
+    fn noon_bean_small_graphs() {
+        let single: Graph<usize> = Graph::from(vec![0], vec![vec![0]]);
+        let pair: Graph<usize> = Graph::from(vec![0, 1], vec![vec![0, 3], vec![7, 0]]);
+
+        assert_eq!(single.gtsp_noon_bean(&Clusters::from_labels(&[0]).unwrap(), Solver::HeldKarp, false), Ok((vec![0], 0)));
+        assert_eq!(pair.gtsp_noon_bean(&Clusters::from_labels(&[0, 0]).unwrap(), Solver::HeldKarp, false).map(|(route, cost)| (route.len(), cost)), Ok((1, 0)));
+        assert_eq!(pair.gtsp_noon_bean(&Clusters::from_labels(&[0, 1]).unwrap(), Solver::HeldKarp, false).map(|(_, cost)| cost), Ok(10));
+        assert!(pair.get_noon_bean(&Clusters::from_labels(&[0]).unwrap()).is_err());
+        assert!(Graph::from(vec![0, 1], vec![vec![0, u64::MAX], vec![1, 0]]).get_noon_bean(&Clusters::from_labels(&[0, 1]).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,105 @@
+// Estrutura de conjuntos disjuntos (union-find) com compressao de caminho e uniao por rank:
+// https://en.wikipedia.org/wiki/Disjoint-set_data_structure
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    num_sets: usize
+}
+
+impl UnionFind {
+    // Cria n conjuntos unitarios {0}, {1}, ..., {n-1}
+    pub fn new(n: usize) -> Self {
+        UnionFind {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            num_sets: n
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    pub fn num_sets(&self) -> usize {
+        self.num_sets
+    }
+
+    // Representante do conjunto de x
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root: usize = x;
+
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // Compressao de caminho: todos no caminho passam a apontar direto para a raiz
+        let mut actual: usize = x;
+
+        while self.parent[actual] != root {
+            let next: usize = self.parent[actual];
+            self.parent[actual] = root;
+            actual = next;
+        }
+
+        root
+    }
+
+    // Junta os conjuntos de x e y, retorna false se ja estavam no mesmo conjunto
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (root_x, root_y) = (self.find(x), self.find(y));
+
+        if root_x == root_y {
+            return false;
+        }
+
+        if self.rank[root_x] < self.rank[root_y] {
+            self.parent[root_x] = root_y;
+        } else if self.rank[root_x] > self.rank[root_y] {
+            self.parent[root_y] = root_x;
+        } else {
+            self.parent[root_y] = root_x;
+            self.rank[root_x] += 1;
+        }
+
+        self.num_sets -= 1;
+
+        true
+    }
+
+    pub fn same_set(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unions_merge_sets() {
+        let mut sets: UnionFind = UnionFind::new(5);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 4));
+        assert!(sets.union(1, 4));
+        assert!(!sets.union(0, 3));
+        assert!(sets.same_set(0, 4));
+        assert!(!sets.same_set(2, 3));
+        assert_eq!(sets.num_sets(), 2);
+        assert_eq!(sets.len(), 5);
+    }
+
+    #[test]
+    fn empty_and_single_sets() {
+        let mut single: UnionFind = UnionFind::new(1);
+
+        assert!(UnionFind::new(0).is_empty());
+        assert_eq!(single.find(0), 0);
+        assert!(!single.union(0, 0));
+        assert_eq!(single.num_sets(), 1);
+    }
+}
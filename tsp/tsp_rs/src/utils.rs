@@ -1,22 +1,159 @@
 use crate::graph::Graph;
-use std::io::{BufRead, BufReader};
+use crate::coordinates::{Coordinates, Metric};
+use crate::time_windows::TimeWindow;
+use crate::precedence::Precedences;
+use std::io::{BufRead, BufReader, Error, ErrorKind};
 use std::fs::File;
 
 pub fn read_tsp_file(filename: &String) -> std::io::Result<Graph<usize>> {
     let file: File = File::open(filename)?;
     let reader: BufReader<File> = BufReader::new(file);
-    let mut vertices: Vec<usize> = Vec::new();
     let mut adjacency_matrix: Vec<Vec<u64>> = Vec::new();
 
     for line in reader.lines() {
         let line = line?;
         let _ = line.replace("  ", "");
 
-        let values: Vec<u64> = line.split_whitespace().map(|x| { x.parse::<u64>().unwrap() } ).collect();
+        let values: Vec<u64> = line.split_whitespace().map(|x| x.parse::<u64>()).collect::<Result<Vec<u64>, _>>()
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "Invalid edge weight!"))?;
         adjacency_matrix.push(values);
     }
 
-    vertices = (0..adjacency_matrix.len()).collect();
+    let vertices: Vec<usize> = (0..adjacency_matrix.len()).collect();
 
-    Ok(Graph::<usize>::from(vertices, adjacency_matrix))
-}
\ No newline at end of file
+    Graph::<usize>::try_from(vertices, adjacency_matrix).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+}
+
+// Le um arquivo TSPLIB com NODE_COORD_SECTION. O cabecalho precisa ter EDGE_WEIGHT_TYPE com uma das metricas
+// suportadas, a matriz de distancias nao é gerada (ver `Graph::from_coordinates`).
+pub fn read_tsplib_coordinates_file(filename: &String) -> std::io::Result<Graph<usize>> {
+    let file: File = File::open(filename)?;
+    let reader: BufReader<File> = BufReader::new(file);
+    let mut metric: Option<Metric> = None;
+    let mut points: Vec<(f64, f64)> = Vec::new();
+    let mut in_coord_section: bool = false;
+
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if line == "EOF" {
+            break;
+        }
+
+        if line.starts_with("NODE_COORD_SECTION") {
+            in_coord_section = true;
+            continue;
+        }
+
+        if !in_coord_section {
+            if let Some((key, value)) = line.split_once(':') {
+                if key.trim() == "EDGE_WEIGHT_TYPE" {
+                    metric = Some(Metric::from_tsplib(value).map_err(|e| Error::new(ErrorKind::InvalidData, e))?);
+                }
+            }
+
+            continue;
+        }
+
+        // Linhas no formato: <id> <x> <y>
+        let values: Vec<f64> = line.split_whitespace().skip(1).map(|x| x.parse::<f64>()).collect::<Result<Vec<f64>, _>>()
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "Invalid coordinate!"))?;
+
+        if values.len() != 2 {
+            return Err(Error::new(ErrorKind::InvalidData, "Invalid coordinate line!"));
+        }
+
+        points.push((values[0], values[1]));
+    }
+
+    let metric: Metric = match metric {
+        Some(m) => m,
+        None => return Err(Error::new(ErrorKind::InvalidData, "Missing EDGE_WEIGHT_TYPE!"))
+    };
+
+    let vertices: Vec<usize> = (0..points.len()).collect();
+
+    Graph::<usize>::from_coordinates(vertices, Coordinates::new(points, metric)).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+}
+
+// Le as janelas de tempo, uma linha por cidade na mesma ordem da matriz: <inicio> <fim> <duracao do servico>.
+// Linhas vazias e comecando com # sao ignoradas.
+pub fn read_time_windows_file(filename: &String) -> std::io::Result<Vec<TimeWindow>> {
+    let file: File = File::open(filename)?;
+    let reader: BufReader<File> = BufReader::new(file);
+    let mut windows: Vec<TimeWindow> = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let values: Vec<u64> = line.split_whitespace().map(|x| x.parse::<u64>()).collect::<Result<Vec<u64>, _>>()
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "Invalid time window!"))?;
+
+        if values.len() != 3 {
+            return Err(Error::new(ErrorKind::InvalidData, "Invalid time window line!"));
+        }
+
+        windows.push(TimeWindow::new(values[0], values[1], values[2]).map_err(|e| Error::new(ErrorKind::InvalidData, e))?);
+    }
+
+    Ok(windows)
+}
+
+// Le um valor inteiro por cidade (demanda, premio), um por linha na mesma ordem da matriz.
+// Linhas vazias e comecando com # sao ignoradas.
+pub fn read_vertex_values_file(filename: &String) -> std::io::Result<Vec<u64>> {
+    let file: File = File::open(filename)?;
+    let reader: BufReader<File> = BufReader::new(file);
+    let mut values: Vec<u64> = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        values.push(line.parse::<u64>().map_err(|_| Error::new(ErrorKind::InvalidData, "Invalid vertex value!"))?);
+    }
+
+    Ok(values)
+}
+
+// Le as precedencias, uma linha '<antes> <depois>' por restricao (por exemplo coleta e entrega).
+// Linhas vazias e comecando com # sao ignoradas.
+pub fn read_precedences_file(filename: &String) -> std::io::Result<Precedences> {
+    let file: File = File::open(filename)?;
+    let reader: BufReader<File> = BufReader::new(file);
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let values: Vec<usize> = line.split_whitespace().map(|x| x.parse::<usize>()).collect::<Result<Vec<usize>, _>>()
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "Invalid precedence!"))?;
+
+        if values.len() != 2 {
+            return Err(Error::new(ErrorKind::InvalidData, "Invalid precedence line!"));
+        }
+
+        pairs.push((values[0], values[1]));
+    }
+
+    Ok(Precedences::new(pairs))
+}
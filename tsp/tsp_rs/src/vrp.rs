@@ -0,0 +1,494 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::time::Instant;
+use crate::graph::Graph;
+use crate::solver::Solver;
+
+// Objetivo das solucoes com varias rotas: custo total (min-sum) ou custo da rota mais longa (min-max, equilibra
+// o trabalho entre os veiculos)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FleetObjective {
+    MinSum,
+    MinMax
+}
+
+impl FleetObjective {
+    // Chave de comparacao a partir do custo de cada rota: o objetivo e, para desempatar, o outro criterio
+    pub fn evaluate<I: Iterator<Item = u64>>(&self, costs: I) -> (u64, u64) {
+        let (sum, max) = costs.fold((0, 0), |(sum, max), c| (sum + c, max.max(c)));
+
+        self.key(sum, max)
+    }
+
+    // Mesma chave a partir da soma e do maximo ja calculados
+    pub fn key(&self, sum: u64, max: u64) -> (u64, u64) {
+        match self {
+            FleetObjective::MinSum => (sum, max),
+            FleetObjective::MinMax => (max, sum)
+        }
+    }
+}
+
+// Rota de um veiculo: sai do deposito, visita os clientes na ordem e volta ao deposito
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VehicleRoute {
+    customers: Vec<usize>,
+    load: u64,
+    cost: u64
+}
+
+impl VehicleRoute {
+    pub fn get_customers(&self) -> &Vec<usize> {
+        &self.customers
+    }
+
+    pub fn get_load(&self) -> u64 {
+        self.load
+    }
+
+    pub fn get_cost(&self) -> u64 {
+        self.cost
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone> Graph<V> {
+    // Custo de deposito -> clientes -> deposito (0 para uma rota vazia)
+    pub fn get_vehicle_route_cost(&self, depot_idx: usize, customers: &[usize]) -> u64 {
+        match (customers.first(), customers.last()) {
+            (Some(&first), Some(&last)) => {
+                self.weight(depot_idx, first)
+                    + customers.windows(2).map(|pair| self.weight(pair[0], pair[1])).sum::<u64>()
+                    + self.weight(last, depot_idx)
+            },
+            _ => 0
+        }
+    }
+
+    // Verificacoes comuns dos algoritmos de roteamento com capacidade
+    fn check_capacitated_instance(&self, depot_idx: usize, demands: &[u64], capacity: u64) -> Result<(), &'static str> {
+        self.check_complete()?;
+
+        if self.num_vertices() <= depot_idx {
+            return Err("Index out of range!");
+        }
+
+        if demands.len() != self.num_vertices() {
+            return Err("There must be one demand per vertex!");
+        }
+
+        if (0..self.num_vertices()).any(|v| v != depot_idx && capacity < demands[v]) {
+            return Err("A customer demand exceeds the vehicle capacity!");
+        }
+
+        Ok(())
+    }
+
+    // A rota gigante dos splits precisa ser uma permutacao das cidades sem o deposito
+    pub(crate) fn check_giant_tour(&self, depot_idx: usize, giant_tour: &[usize]) -> Result<(), &'static str> {
+        let mut visited: Vec<bool> = (0..self.num_vertices()).map(|v| v == depot_idx).collect();
+
+        if giant_tour.len() + 1 != self.num_vertices() || giant_tour.iter().any(|&v| self.num_vertices() <= v || std::mem::replace(&mut visited[v], true)) {
+            return Err("Giant tour must visit every customer once!");
+        }
+
+        Ok(())
+    }
+
+    // Algoritmo de economias de Clarke-Wright: https://en.wikipedia.org/wiki/Clarke_and_Wright_savings_algorithm
+    // Comeca com uma rota por cliente e junta a rota que termina em i com a que comeca em j, em ordem
+    // decrescente da economia d(i, deposito) + d(deposito, j) - d(i, j), enquanto a carga couber no veiculo.
+    // Os arcos sao usados no sentido da rota, entao vale para matrizes assimetricas.
+    pub fn cvrp_clarke_wright(&self, depot_idx: usize, demands: &[u64], capacity: u64) -> Result<Vec<Vec<usize>>, &'static str> {
+        self.check_capacitated_instance(depot_idx, demands, capacity)?;
+
+        let n: usize = self.num_vertices();
+        let customers: Vec<usize> = (0..n).filter(|&v| v != depot_idx).collect();
+        let mut routes: Vec<Option<Vec<usize>>> = (0..n).map(|v| if v == depot_idx { None } else { Some(vec![v]) }).collect();
+        let mut loads: Vec<u64> = demands.to_vec();
+        // Rota (indexada pelo cliente que a criou) em que cada cliente esta
+        let mut route_of: Vec<usize> = (0..n).collect();
+
+        let mut savings: Vec<(i64, usize, usize)> = customers.iter()
+            .flat_map(|&i| customers.iter().filter(move |&&j| j != i).map(move |&j| (i, j)))
+            .map(|(i, j)| (self.weight(i, depot_idx) as i64 + self.weight(depot_idx, j) as i64 - self.weight(i, j) as i64, i, j))
+            .filter(|&(saving, _, _)| saving >= 0)
+            .collect();
+        savings.sort_unstable_by(|a, b| b.cmp(a));
+
+        for (_, i, j) in savings {
+            let (route_i, route_j) = (route_of[i], route_of[j]);
+
+            if route_i == route_j || capacity < loads[route_i] + loads[route_j] {
+                continue;
+            }
+
+            let ends_at_i: bool = routes[route_i].as_ref().is_some_and(|r| r.last() == Some(&i));
+            let starts_at_j: bool = routes[route_j].as_ref().is_some_and(|r| r.first() == Some(&j));
+
+            if !ends_at_i || !starts_at_j {
+                continue;
+            }
+
+            let tail: Vec<usize> = routes[route_j].take().unwrap();
+
+            for &v in tail.iter() {
+                route_of[v] = route_i;
+            }
+
+            routes[route_i].as_mut().unwrap().extend(tail);
+            loads[route_i] += loads[route_j];
+        }
+
+        Ok(routes.into_iter().flatten().collect())
+    }
+
+    // Split de Prins (route-first, cluster-second): divide uma rota gigante com todos os clientes em rotas de
+    // veiculos consecutivas, escolhendo os cortes de menor custo total por programacao dinamica. O(n²).
+    pub fn cvrp_split(&self, depot_idx: usize, demands: &[u64], capacity: u64, giant_tour: &[usize]) -> Result<Vec<Vec<usize>>, &'static str> {
+        self.check_capacitated_instance(depot_idx, demands, capacity)?;
+
+        self.check_giant_tour(depot_idx, giant_tour)?;
+
+        let k: usize = giant_tour.len();
+        // best[j]: menor custo para atender os j primeiros clientes; cut[j]: onde comeca a ultima rota
+        let mut best: Vec<Option<u64>> = vec![None; k + 1];
+        let mut cut: Vec<usize> = vec![0; k + 1];
+        best[0] = Some(0);
+
+        for i in 0..k {
+            let Some(base) = best[i] else { continue };
+            let mut load: u64 = 0;
+            let mut inner: u64 = 0;
+
+            for j in i + 1..=k {
+                load += demands[giant_tour[j - 1]];
+
+                if capacity < load {
+                    break;
+                }
+
+                if j > i + 1 {
+                    inner += self.weight(giant_tour[j - 2], giant_tour[j - 1]);
+                }
+
+                let cost: u64 = base + self.weight(depot_idx, giant_tour[i]) + inner + self.weight(giant_tour[j - 1], depot_idx);
+
+                if best[j].is_none_or(|actual| cost < actual) {
+                    best[j] = Some(cost);
+                    cut[j] = i;
+                }
+            }
+        }
+
+        let mut routes: Vec<Vec<usize>> = Vec::new();
+        let mut j: usize = k;
+
+        while j > 0 {
+            routes.push(giant_tour[cut[j]..j].to_vec());
+            j = cut[j];
+        }
+
+        routes.reverse();
+
+        Ok(routes)
+    }
+
+    // Busca local sobre varias rotas que saem do mesmo deposito. Dentro de cada rota usa or-opt e 3-opt sem
+    // inversao; entre rotas tenta mover um cliente (relocate), trocar dois clientes (exchange) e trocar os
+    // finais de duas rotas (2-opt*). Só aceita rotas para as quais `is_feasible` vale e melhora o `objective`.
+    // O numero de rotas nao muda: uma rota pode ficar vazia e voltar a receber clientes. Retorna se alguma
+    // rota mudou.
+    pub fn improve_vehicle_routes<F: Fn(&[usize]) -> bool>(&self, depot_idx: usize, routes: &mut [Vec<usize>], objective: FleetObjective, is_feasible: F) -> bool {
+        let cost = |route: &[usize]| -> u64 { self.get_vehicle_route_cost(depot_idx, route) };
+        let mut costs: Vec<u64> = routes.iter().map(|r| cost(r)).collect();
+        let mut improved: bool = false;
+
+        // Valor do objetivo trocando o custo das rotas a e b
+        let evaluate = |costs: &[u64], (a, cost_a): (usize, u64), (b, cost_b): (usize, u64)| -> (u64, u64) {
+            objective.evaluate(costs.iter().enumerate().map(|(r, &c)| if r == a { cost_a } else if r == b { cost_b } else { c }))
+        };
+
+        'search: loop {
+            // Dentro de cada rota, com o deposito como primeira cidade do ciclo. Melhorar uma rota nunca piora
+            // nenhum dos objetivos.
+            for (route, route_cost) in routes.iter_mut().zip(costs.iter_mut()) {
+                let mut tour: Vec<usize> = [depot_idx].into_iter().chain(route.iter().copied()).collect();
+
+                while self.improve_segment_insertion(&mut tour) | self.improve_or_opt(&mut tour) {}
+
+                let depot_position: usize = tour.iter().position(|&v| v == depot_idx).unwrap();
+                tour.rotate_left(depot_position);
+
+                if cost(&tour[1..]) < *route_cost {
+                    *route = tour[1..].to_vec();
+                    *route_cost = cost(route);
+                    improved = true;
+                }
+            }
+
+            let current: (u64, u64) = objective.evaluate(costs.iter().copied());
+
+            for a in 0..routes.len() {
+                for b in (0..routes.len()).filter(|&b| b != a) {
+                    let mut candidates: Vec<(Vec<usize>, Vec<usize>)> = Vec::new();
+
+                    // Relocate: cliente i de a para a posicao p de b
+                    for i in 0..routes[a].len() {
+                        let mut new_a: Vec<usize> = routes[a].clone();
+                        let customer: usize = new_a.remove(i);
+
+                        for p in 0..=routes[b].len() {
+                            let mut new_b: Vec<usize> = routes[b].clone();
+                            new_b.insert(p, customer);
+                            candidates.push((new_a.clone(), new_b));
+                        }
+                    }
+
+                    // Exchange: cliente i de a com o cliente j de b (cada par testado uma vez)
+                    if a < b {
+                        for i in 0..routes[a].len() {
+                            for j in 0..routes[b].len() {
+                                let mut new_a: Vec<usize> = routes[a].clone();
+                                let mut new_b: Vec<usize> = routes[b].clone();
+                                std::mem::swap(&mut new_a[i], &mut new_b[j]);
+                                candidates.push((new_a, new_b));
+                            }
+                        }
+                    }
+
+                    // 2-opt*: a fica com o inicio de a e o final de b, e b com o inicio de b e o final de a
+                    for i in 0..=routes[a].len() {
+                        for j in 0..=routes[b].len() {
+                            let new_a: Vec<usize> = routes[a][..i].iter().chain(routes[b][j..].iter()).copied().collect();
+                            let new_b: Vec<usize> = routes[b][..j].iter().chain(routes[a][i..].iter()).copied().collect();
+                            candidates.push((new_a, new_b));
+                        }
+                    }
+
+                    for (new_a, new_b) in candidates {
+                        let (cost_a, cost_b) = (cost(&new_a), cost(&new_b));
+
+                        if evaluate(&costs, (a, cost_a), (b, cost_b)) < current && is_feasible(&new_a) && is_feasible(&new_b) {
+                            routes[a] = new_a;
+                            routes[b] = new_b;
+                            costs[a] = cost_a;
+                            costs[b] = cost_b;
+                            improved = true;
+                            continue 'search;
+                        }
+                    }
+                }
+            }
+
+            return improved;
+        }
+    }
+
+    // CVRP: clientes com demanda, veiculos com capacidade `capacity` saindo e voltando a `depot_idx` (a demanda
+    // do deposito é ignorada). Constroi uma solucao com Clarke-Wright e outra com o split da rota do 3-opt,
+    // melhora as duas com a busca local entre rotas e fica com a mais barata.
+    pub fn cvrp_approx(&self, depot_idx: usize, demands: &[u64], capacity: u64, log: bool) -> Result<Vec<VehicleRoute>, &'static str> {
+        self.check_capacitated_instance(depot_idx, demands, capacity)?;
+
+        let start_time = Instant::now();
+
+        let load = |route: &[usize]| -> u64 { route.iter().map(|&v| demands[v]).sum() };
+        let total_cost = |routes: &Vec<Vec<usize>>| -> u64 { routes.iter().map(|r| self.get_vehicle_route_cost(depot_idx, r)).sum() };
+
+        let (mut giant_tour, _) = self.solve(Solver::ThreeOpt, false)?;
+        let depot_position: usize = giant_tour.iter().position(|&v| v == depot_idx).unwrap();
+        giant_tour.rotate_left(depot_position);
+        giant_tour.remove(0);
+
+        let mut candidates: Vec<(&str, Vec<Vec<usize>>)> = vec![
+            ("Clarke-Wright", self.cvrp_clarke_wright(depot_idx, demands, capacity)?),
+            ("Split", self.cvrp_split(depot_idx, demands, capacity, &giant_tour)?)
+        ];
+
+        for (name, routes) in candidates.iter_mut() {
+            if log {
+                println!("{} cost: {} - Vehicles: {}", name, total_cost(routes), routes.len());
+            }
+
+            self.improve_vehicle_routes(depot_idx, routes, FleetObjective::MinSum, |route| load(route) <= capacity);
+            routes.retain(|route| !route.is_empty());
+
+            if log {
+                println!("{} after local search: {} - Vehicles: {}", name, total_cost(routes), routes.len());
+            }
+        }
+
+        let (_, routes) = candidates.into_iter().min_by_key(|(_, routes)| total_cost(routes)).unwrap();
+
+        let vehicle_routes: Vec<VehicleRoute> = routes.into_iter()
+            .map(|customers| VehicleRoute {
+                load: load(&customers),
+                cost: self.get_vehicle_route_cost(depot_idx, &customers),
+                customers
+            })
+            .collect();
+
+        let end_time = Instant::now();
+
+        if log {
+            println!("Time elapsed: {} - Total cost: {} - Vehicles: {}", end_time.duration_since(start_time).as_micros(), vehicle_routes.iter().map(|r| r.cost).sum::<u64>(), vehicle_routes.len());
+        }
+
+        Ok(vehicle_routes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{next_random, random_graph};
+    use itertools::Itertools;
+
+    fn total_cost(t: &Graph<usize>, depot_idx: usize, routes: &[Vec<usize>]) -> u64 {
+        routes.iter().map(|r| t.get_vehicle_route_cost(depot_idx, r)).sum()
+    }
+
+    // Confere que as rotas atendem cada cliente uma vez sem passar da capacidade
+    fn is_valid_solution(routes: &[Vec<usize>], n: usize, depot_idx: usize, demands: &[u64], capacity: u64) -> bool {
+        routes.iter().all(|r| r.iter().map(|&v| demands[v]).sum::<u64>() <= capacity)
+            && routes.iter().flatten().copied().sorted().eq((0..n).filter(|&v| v != depot_idx))
+    }
+
+    // Melhor divisao da rota gigante testando todos os conjuntos de cortes
+    fn brute_force_split(t: &Graph<usize>, depot_idx: usize, demands: &[u64], capacity: u64, giant_tour: &[usize]) -> Option<u64> {
+        let k: usize = giant_tour.len();
+
+        (0..1usize << k.saturating_sub(1))
+            .filter_map(|mask| {
+                let mut routes: Vec<Vec<usize>> = vec![Vec::new()];
+
+                for (i, &v) in giant_tour.iter().enumerate() {
+                    if i > 0 && mask & (1 << (i - 1)) != 0 {
+                        routes.push(Vec::new());
+                    }
+
+                    routes.last_mut().unwrap().push(v);
+                }
+
+                routes.iter().all(|r| r.iter().map(|&v| demands[v]).sum::<u64>() <= capacity).then(|| total_cost(t, depot_idx, &routes))
+            })
+            .min()
+    }
+
+    // Toda solucao do CVRP é a divisao de alguma ordem dos clientes
+    fn brute_force_cvrp(t: &Graph<usize>, depot_idx: usize, demands: &[u64], capacity: u64) -> u64 {
+        let customers: Vec<usize> = (0..t.num_vertices()).filter(|&v| v != depot_idx).collect();
+
+        customers.iter().copied().permutations(customers.len())
+            .filter_map(|p| brute_force_split(t, depot_idx, demands, capacity, &p))
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn fleet_objective_keys() {
+        assert_eq!(FleetObjective::MinSum.evaluate([3, 7, 2].into_iter()), (12, 7));
+        assert_eq!(FleetObjective::MinMax.evaluate([3, 7, 2].into_iter()), (7, 12));
+        assert_eq!(FleetObjective::MinMax.evaluate(std::iter::empty()), (0, 0));
+    }
+
+    #[test]
+    fn vehicle_route_cost_known_answer() {
+        let t: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0, 1, 5], vec![2, 0, 3], vec![4, 6, 0]]);
+
+        assert_eq!(t.get_vehicle_route_cost(0, &[1, 2]), 1 + 3 + 4);
+        assert_eq!(t.get_vehicle_route_cost(0, &[2, 1]), 5 + 6 + 2);
+        assert_eq!(t.get_vehicle_route_cost(0, &[1]), 3);
+        assert_eq!(t.get_vehicle_route_cost(0, &[]), 0);
+    }
+
+    #[test]
+    fn split_matches_brute_force() {
+        let mut seed: u64 = 45;
+
+        for n in [2, 3, 5, 8] {
+            for symmetric in [true, false] {
+                let t: Graph<usize> = random_graph(n, symmetric, &mut seed);
+                let demands: Vec<u64> = (0..n).map(|_| 1 + next_random(&mut seed) % 4).collect();
+                let giant_tour: Vec<usize> = (1..n).rev().collect();
+
+                for capacity in [4, 6, 100] {
+                    let routes: Vec<Vec<usize>> = t.cvrp_split(0, &demands, capacity, &giant_tour).unwrap();
+
+                    assert!(is_valid_solution(&routes, n, 0, &demands, capacity));
+                    assert_eq!(routes.concat(), giant_tour);
+                    assert_eq!(Some(total_cost(&t, 0, &routes)), brute_force_split(&t, 0, &demands, capacity, &giant_tour));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn heuristics_return_valid_solutions() {
+        let mut seed: u64 = 46;
+
+        for n in [2, 4, 6, 7] {
+            for symmetric in [true, false] {
+                let t: Graph<usize> = random_graph(n, symmetric, &mut seed);
+                let demands: Vec<u64> = (0..n).map(|_| 1 + next_random(&mut seed) % 4).collect();
+                let depot_idx: usize = n / 2;
+
+                for capacity in [4, 7, 100] {
+                    let optimum: u64 = brute_force_cvrp(&t, depot_idx, &demands, capacity);
+                    let savings: Vec<Vec<usize>> = t.cvrp_clarke_wright(depot_idx, &demands, capacity).unwrap();
+                    let approx: Vec<VehicleRoute> = t.cvrp_approx(depot_idx, &demands, capacity, false).unwrap();
+                    let routes: Vec<Vec<usize>> = approx.iter().map(|r| r.get_customers().clone()).collect();
+
+                    assert!(is_valid_solution(&savings, n, depot_idx, &demands, capacity));
+                    assert!(is_valid_solution(&routes, n, depot_idx, &demands, capacity));
+                    assert!(approx.iter().all(|r| r.get_cost() == t.get_vehicle_route_cost(depot_idx, r.get_customers()) && !r.get_customers().is_empty()));
+                    assert!(total_cost(&t, depot_idx, &savings) >= optimum);
+                    assert!(total_cost(&t, depot_idx, &routes) >= optimum);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn full_customers_need_one_vehicle_each() {
+        // Cada cliente enche o veiculo, entao a unica solucao é uma ida e volta por cliente
+        let t: Graph<usize> = Graph::from((0..4).collect(), vec![
+            vec![0, 2, 3, 4],
+            vec![2, 0, 1, 1],
+            vec![3, 1, 0, 1],
+            vec![4, 1, 1, 0]
+        ]);
+        let demands: Vec<u64> = vec![0, 5, 5, 5];
+        let routes: Vec<VehicleRoute> = t.cvrp_approx(0, &demands, 5, false).unwrap();
+
+        assert_eq!(routes.len(), 3);
+        assert!(routes.iter().all(|r| r.get_load() == 5 && r.get_customers().len() == 1));
+        assert_eq!(routes.iter().map(|r| r.get_cost()).sum::<u64>(), 18);
+
+        // Com capacidade para todos, uma rota so atende os tres clientes pelo caminho mais curto
+        let routes: Vec<VehicleRoute> = t.cvrp_approx(0, &demands, 15, false).unwrap();
+
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].get_cost(), 2 + 1 + 1 + 3);
+    }
+
+    #[test]
+    fn small_and_invalid_instances() {
+        let pair: Graph<usize> = Graph::from(vec![0, 1], vec![vec![0, 3], vec![7, 0]]);
+        let routes: Vec<VehicleRoute> = pair.cvrp_approx(0, &[0, 2], 2, false).unwrap();
+
+        assert_eq!(routes, vec![VehicleRoute { customers: vec![1], load: 2, cost: 10 }]);
+        assert_eq!(pair.cvrp_split(1, &[4, 0], 4, &[0]), Ok(vec![vec![0]]));
+
+        assert!(pair.cvrp_approx(0, &[0, 3], 2, false).is_err());
+        assert!(pair.cvrp_approx(2, &[0, 2], 2, false).is_err());
+        assert!(pair.cvrp_approx(0, &[0], 2, false).is_err());
+        assert!(pair.cvrp_split(0, &[0, 2], 2, &[]).is_err());
+        assert!(pair.cvrp_split(0, &[0, 2], 2, &[0]).is_err());
+        assert!(pair.cvrp_split(0, &[0, 2], 2, &[1, 1]).is_err());
+
+        let triangle: Graph<usize> = Graph::from(vec![0, 1, 2], vec![vec![0, 1, 2], vec![1, 0, 3], vec![2, 3, 0]]);
+        assert!(triangle.cvrp_split(0, &[0, 1, 1], 2, &[1, 1]).is_err());
+        assert!(triangle.cvrp_split(0, &[0, 1, 1], 2, &[2, 1]).is_ok());
+    }
+}